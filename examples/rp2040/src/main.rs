@@ -8,7 +8,7 @@ use embassy_rp::{
     peripherals::I2C0,
 };
 use embassy_time::Timer;
-use emc230x::{Emc230x, EMC2301_I2C_ADDR};
+use emc230x::{AsyncEmc230x, EMC2301_I2C_ADDR};
 use fans::FanSelect;
 use {defmt_rtt as _, panic_probe as _};
 
@@ -16,7 +16,7 @@ bind_interrupts!(struct Irqs {
     I2C0_IRQ => InterruptHandler<I2C0>;
 });
 
-async fn print_fan_info(dev: &mut Emc230x<EmbassyI2c<'_, I2C0, Async>>) {
+async fn print_fan_info(dev: &mut AsyncEmc230x<EmbassyI2c<'_, I2C0, Async>>) {
     let fan_select = FanSelect(1);
     let duty_cycle = dev.duty_cycle(fan_select).await.unwrap();
     let rpm = dev.rpm(fan_select).await.unwrap();
@@ -30,8 +30,8 @@ async fn main(_spawner: Spawner) {
     let scl = p.PIN_1;
 
     let i2c = i2c::I2c::new_async(p.I2C0, scl, sda, Irqs, i2c::Config::default());
-    let mut emc230x: Emc230x<EmbassyI2c<'_, I2C0, Async>> =
-        Emc230x::new(i2c, EMC2301_I2C_ADDR).await.unwrap();
+    let mut emc230x: AsyncEmc230x<EmbassyI2c<'_, I2C0, Async>> =
+        AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR).await.unwrap();
 
     defmt::info!("EMC2301 Fan Controller Example");
 
@@ -29,6 +29,7 @@ pub fn derive_register_address(input: TokenStream) -> TokenStream {
         const ADDRESS: u8 = #address_value;
     };
 
+    let formatting = formatting(&ident);
     let output = quote! {
         impl RegisterAddress for #ident {
             #address
@@ -51,6 +52,8 @@ pub fn derive_register_address(input: TokenStream) -> TokenStream {
                 Self(val)
             }
         }
+
+        #formatting
     };
 
     output.into()
@@ -75,6 +78,7 @@ pub fn derive_register_offset(input: TokenStream) -> TokenStream {
         const OFFSET: u8 = #offset_value;
     };
 
+    let formatting = formatting(&ident);
     let output = quote! {
         impl RegisterOffset for #ident {
             #offset
@@ -97,7 +101,30 @@ pub fn derive_register_offset(input: TokenStream) -> TokenStream {
                 Self(val)
             }
         }
+
+        #formatting
     };
 
     output.into()
 }
+
+/// `Display` and `defmt::Format` implementations showing the raw value of a register
+fn formatting(ident: &syn::Ident) -> proc_macro2::TokenStream {
+    let display = format!("{ident}({{:#04x}})");
+    let format = format!("{ident}({{=u8:#04x}})");
+
+    quote! {
+        impl core::fmt::Display for #ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, #display, self.0)
+            }
+        }
+
+        #[cfg(feature = "defmt")]
+        impl defmt::Format for #ident {
+            fn format(&self, f: defmt::Formatter) {
+                defmt::write!(f, #format, self.0)
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    registers::{Edges, Range},
+    FanControl, FanSelect, FanSet, OutputMode,
+};
+
+/// Initial configuration of a single fan
+pub(crate) struct FanInit {
+    pub(crate) output_mode: OutputMode,
+    pub(crate) range: Range,
+    pub(crate) edges: Option<Edges>,
+    pub(crate) poles: u8,
+    pub(crate) auto_range: bool,
+    pub(crate) pwm_frequency: Option<u32>,
+    pub(crate) mode: Option<FanControl>,
+}
+
+impl Default for FanInit {
+    fn default() -> Self {
+        Self {
+            output_mode: OutputMode::PushPull,
+            range: Range::Rpm500,
+            edges: None,
+            poles: 2,
+            auto_range: false,
+            pwm_frequency: None,
+            mode: None,
+        }
+    }
+}
+
+/// Configuration written to an EMC230x device when the driver is created
+///
+/// Every fan defaults to a push-pull output, the 500 RPM range and 2 poles, which is the
+/// configuration applied by `new`. The number of edges, PWM frequency and mode of a fan are left
+/// as found on the device unless they are selected.
+///
+/// Nothing is written until the builder is passed to `with_builder`, which also rejects fans the
+/// device does not support before the first write.
+#[derive(Default)]
+pub struct Emc230xBuilder {
+    /// Initial configuration of fans 1 through 5
+    pub(crate) fans: [FanInit; 5],
+
+    /// Fans which have been configured, including fans no device supports
+    pub(crate) selected: FanSet,
+}
+
+impl Emc230xBuilder {
+    /// Create a builder with the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select the PWM output driver of a fan
+    pub fn output_mode(mut self, sel: FanSelect, mode: OutputMode) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.output_mode = mode;
+        }
+        self
+    }
+
+    /// Select the RPM range of a fan
+    pub fn range(mut self, sel: FanSelect, range: Range) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.range = range;
+        }
+        self
+    }
+
+    /// Select the number of TACH edges sampled for a fan
+    pub fn edges(mut self, sel: FanSelect, edges: Edges) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.edges = Some(edges);
+        }
+        self
+    }
+
+    /// Select the number of poles of a fan (used in RPM calculations)
+    pub fn poles(mut self, sel: FanSelect, poles: u8) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.poles = poles;
+        }
+        self
+    }
+
+    /// Select the RPM range of a fan automatically for each target RPM
+    pub fn auto_range(mut self, sel: FanSelect, enabled: bool) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.auto_range = enabled;
+        }
+        self
+    }
+
+    /// Select the PWM output frequency (Hz) of a fan
+    pub fn pwm_frequency(mut self, sel: FanSelect, hz: u32) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.pwm_frequency = Some(hz);
+        }
+        self
+    }
+
+    /// Select the initial mode of a fan
+    pub fn mode(mut self, sel: FanSelect, mode: FanControl) -> Self {
+        if let Some(fan) = self.fan(sel) {
+            fan.mode = Some(mode);
+        }
+        self
+    }
+
+    fn fan(&mut self, sel: FanSelect) -> Option<&mut FanInit> {
+        self.selected.insert(sel);
+        match sel.0 {
+            1..=5 => Some(&mut self.fans[sel.0 as usize - 1]),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::registers::{
+    Configuration, FanDriveFailStatus, FanDriveSetting, FanStatus, RegisterAddress, RegisterOffset,
+    TachReadingHigh, TachReadingLow, FAN1_BASE,
+};
+
+/// Shadow copy of the read/write registers of the device
+///
+/// Only registers which the device never changes on its own are cached. The status registers,
+/// the TACH Readings and the Fan Drive Settings, which the RPM control algorithm and the Watchdog
+/// Timer update, are always read from the device.
+#[derive(Clone, Debug)]
+pub(crate) struct RegisterCache {
+    values: [u8; Self::LEN],
+    valid: u128,
+}
+
+impl RegisterCache {
+    /// First register held by the cache
+    const FIRST: u8 = Configuration::ADDRESS;
+
+    /// Number of registers held by the cache, up to the last register of fan 5
+    const LEN: usize = 0x60;
+
+    /// Size of the register block of each fan
+    const FAN_BLOCK: u8 = 0x10;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            values: [0; Self::LEN],
+            valid: 0,
+        }
+    }
+
+    /// Cached value of a register, if it is known
+    pub(crate) fn get(&self, reg: u8) -> Option<u8> {
+        let index = Self::index(reg)?;
+        (self.valid & (1 << index) != 0).then_some(self.values[index])
+    }
+
+    /// Record the value of a register, ignoring registers which are not cached
+    pub(crate) fn set(&mut self, reg: u8, value: u8) {
+        if let Some(index) = Self::index(reg) {
+            self.values[index] = value;
+            self.valid |= 1 << index;
+        }
+    }
+
+    /// Record the values of consecutive registers starting at `reg`
+    pub(crate) fn set_all(&mut self, reg: u8, values: &[u8]) {
+        for (reg, value) in (reg..=u8::MAX).zip(values) {
+            self.set(reg, *value);
+        }
+    }
+
+    /// Cached values of consecutive registers starting at `reg`, if they are all known
+    pub(crate) fn get_all(&self, reg: u8, values: &mut [u8]) -> bool {
+        let regs = (reg..=u8::MAX).take(values.len());
+        if !regs.clone().all(|reg| self.get(reg).is_some()) {
+            return false;
+        }
+
+        for (value, reg) in values.iter_mut().zip(regs) {
+            *value = self.get(reg).unwrap_or_default();
+        }
+        true
+    }
+
+    /// Forget every cached value
+    pub(crate) fn invalidate(&mut self) {
+        self.valid = 0;
+    }
+
+    /// Index of a cached register
+    fn index(reg: u8) -> Option<usize> {
+        let index = usize::from(reg.checked_sub(Self::FIRST)?);
+        if index >= Self::LEN || (FanStatus::ADDRESS..=FanDriveFailStatus::ADDRESS).contains(&reg) {
+            return None;
+        }
+
+        if reg >= FAN1_BASE {
+            let offset = (reg - FAN1_BASE) % Self::FAN_BLOCK;
+            let volatile = [
+                FanDriveSetting::OFFSET,
+                TachReadingHigh::OFFSET,
+                TachReadingLow::OFFSET,
+            ];
+            if volatile.contains(&offset) {
+                return None;
+            }
+        }
+
+        Some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegisterCache;
+    use crate::registers::{
+        Configuration, FanConfiguration1, FanDriveSetting, FanStatus, RegisterAddress,
+        RegisterOffset, SoftwareLock, TachReadingHigh, TachTargetLow,
+    };
+
+    #[test]
+    fn register_cache() {
+        let mut cache = RegisterCache::new();
+        assert_eq!(cache.get(FanConfiguration1::FAN5_ADDRESS), None);
+
+        cache.set(Configuration::ADDRESS, 0x40);
+        cache.set(FanConfiguration1::FAN5_ADDRESS, 0x2B);
+        assert_eq!(cache.get(Configuration::ADDRESS), Some(0x40));
+        assert_eq!(cache.get(FanConfiguration1::FAN5_ADDRESS), Some(0x2B));
+
+        // Registers the device changes on its own are never cached
+        for reg in [
+            FanStatus::ADDRESS,
+            FanDriveSetting::FAN2_ADDRESS,
+            TachReadingHigh::FAN3_ADDRESS,
+            SoftwareLock::ADDRESS,
+        ] {
+            cache.set(reg, 0x01);
+            assert_eq!(cache.get(reg), None);
+        }
+
+        let mut values = [0; 2];
+        assert!(!cache.get_all(TachTargetLow::FAN1_ADDRESS, &mut values));
+        cache.set_all(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
+        assert!(cache.get_all(TachTargetLow::FAN1_ADDRESS, &mut values));
+        assert_eq!(values, [0x00, 0x78]);
+
+        cache.invalidate();
+        assert_eq!(cache.get(Configuration::ADDRESS), None);
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Diagnostics facade of the driver.
+//!
+//! Messages are sent to `defmt` and `log` when their features are enabled, and are discarded
+//! otherwise. The format string must be understood by both backends, so only plain `{}` and
+//! integer hints such as `{:#04x}` are used.
+
+/// Log an informational message
+macro_rules! info {
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "defmt")]
+        ::defmt::info!($fmt $(, $arg)*);
+        #[cfg(feature = "log")]
+        ::log::info!($fmt $(, $arg)*);
+        #[cfg(not(any(feature = "defmt", feature = "log")))]
+        let _ = ($(&$arg,)*);
+    }};
+}
@@ -5,12 +5,29 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+#[cfg(feature = "sync")]
+use embedded_hal::i2c::ErrorKind;
+#[cfg(all(feature = "async", not(feature = "sync")))]
+use embedded_hal_async::i2c::ErrorKind;
 use thiserror::Error;
 
-#[derive(Clone, Copy, Debug, Error)]
+use crate::registers::{FAN1_BASE, FAN5_BASE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("I2C bus error")]
-    I2c,
+    /// A bus transaction failed.
+    #[error("I2C bus error accessing register {register:#04x}: {kind}")]
+    I2c {
+        /// Kind of the error reported by the I2C bus.
+        kind: ErrorKind,
+
+        /// First register accessed by the transaction.
+        register: u8,
+
+        /// Fan the register belongs to, if it is specific to a fan.
+        fan: Option<u8>,
+    },
 
     #[error("Invalid device identifier")]
     InvalidDeviceId,
@@ -23,16 +40,102 @@ pub enum Error {
 
     #[error("Failed to convert register value to specific type")]
     RegisterTypeConversion,
+
+    #[error("Alert pin error")]
+    AlertPin,
+
+    #[error("Device configuration is software locked")]
+    SoftwareLocked,
+
+    #[error("Spin-up level is outside of the supported range")]
+    InvalidSpinUpLevel,
+
+    #[error("Spin-up time is outside of the supported range")]
+    InvalidSpinUpTime,
+
+    #[error("Error window is not supported")]
+    InvalidErrorWindow,
+
+    #[error("PWM frequency is outside of the supported range")]
+    InvalidPwmFrequency,
+
+    #[error("Duty cycle is greater than 100%")]
+    InvalidDutyCycle,
+
+    #[error("Ramp rate is outside of the supported range")]
+    InvalidRampRate,
+
+    #[error("RPM is outside of the supported range of {min} to {max} RPM")]
+    RpmOutOfRange { min: u16, max: u16 },
+}
+
+impl Error {
+    /// Size of the register block of each fan
+    const FAN_BLOCK: u8 = 0x10;
+
+    /// Error for a failed bus transaction accessing `register`
+    pub(crate) fn i2c(kind: ErrorKind, register: u8) -> Self {
+        let fan = (FAN1_BASE..FAN5_BASE + Self::FAN_BLOCK)
+            .contains(&register)
+            .then(|| (register - FAN1_BASE) / Self::FAN_BLOCK + 1);
+
+        Error::I2c {
+            kind,
+            register,
+            fan,
+        }
+    }
+}
+
+/// Name of an I2C error kind, as `ErrorKind` does not implement `defmt::Format`
+#[cfg(feature = "defmt")]
+fn kind_name(kind: &ErrorKind) -> &'static str {
+    #[cfg(feature = "sync")]
+    use embedded_hal::i2c::NoAcknowledgeSource;
+    #[cfg(all(feature = "async", not(feature = "sync")))]
+    use embedded_hal_async::i2c::NoAcknowledgeSource;
+
+    match kind {
+        ErrorKind::Bus => "Bus",
+        ErrorKind::ArbitrationLoss => "ArbitrationLoss",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => "NoAcknowledge(Address)",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => "NoAcknowledge(Data)",
+        ErrorKind::NoAcknowledge(_) => "NoAcknowledge(Unknown)",
+        ErrorKind::Overrun => "Overrun",
+        _ => "Other",
+    }
 }
 
+#[cfg(feature = "defmt")]
 impl defmt::Format for Error {
     fn format(&self, f: defmt::Formatter) {
         match self {
-            Error::I2c => defmt::write!(f, "I2c"),
+            Error::I2c {
+                kind,
+                register,
+                fan,
+            } => defmt::write!(
+                f,
+                "I2c {{ kind: {}, register: {=u8:#04x}, fan: {} }}",
+                kind_name(kind),
+                register,
+                fan
+            ),
             Error::InvalidDeviceId => defmt::write!(f, "InvalidDeviceId"),
             Error::InvalidManufacturerId => defmt::write!(f, "InvalidManufacturerId"),
             Error::InvalidFan => defmt::write!(f, "InvalidFan"),
             Error::RegisterTypeConversion => defmt::write!(f, "RegisterTypeConversion"),
+            Error::AlertPin => defmt::write!(f, "AlertPin"),
+            Error::SoftwareLocked => defmt::write!(f, "SoftwareLocked"),
+            Error::InvalidSpinUpLevel => defmt::write!(f, "InvalidSpinUpLevel"),
+            Error::InvalidSpinUpTime => defmt::write!(f, "InvalidSpinUpTime"),
+            Error::InvalidErrorWindow => defmt::write!(f, "InvalidErrorWindow"),
+            Error::InvalidPwmFrequency => defmt::write!(f, "InvalidPwmFrequency"),
+            Error::InvalidDutyCycle => defmt::write!(f, "InvalidDutyCycle"),
+            Error::InvalidRampRate => defmt::write!(f, "InvalidRampRate"),
+            Error::RpmOutOfRange { min, max } => {
+                defmt::write!(f, "RpmOutOfRange {{ min: {}, max: {} }}", min, max)
+            }
         }
     }
 }
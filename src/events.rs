@@ -0,0 +1,138 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use core::fmt::{self, Debug, Formatter};
+
+use crate::{FanSelect, Faults};
+
+/// An event which caused the device to assert the !ALERT pin
+#[derive(Clone, Copy)]
+pub enum FanEvent {
+    /// The fan stalled.
+    Stall(FanSelect),
+
+    /// The spin-up routine failed to start the fan.
+    SpinUpFailed(FanSelect),
+
+    /// The fan cannot reach its target RPM at 100% duty cycle.
+    DriveFail(FanSelect),
+
+    /// The Watchdog Timer expired and every fan is driven at 100% duty cycle.
+    WatchdogExpired,
+}
+
+impl FanEvent {
+    /// The fan the event applies to, if the event is specific to a fan
+    pub fn fan(&self) -> Option<FanSelect> {
+        match self {
+            FanEvent::Stall(fan) | FanEvent::SpinUpFailed(fan) | FanEvent::DriveFail(fan) => {
+                Some(*fan)
+            }
+            FanEvent::WatchdogExpired => None,
+        }
+    }
+}
+
+impl Debug for FanEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FanEvent::Stall(fan) => f.debug_tuple("Stall").field(&fan.0).finish(),
+            FanEvent::SpinUpFailed(fan) => f.debug_tuple("SpinUpFailed").field(&fan.0).finish(),
+            FanEvent::DriveFail(fan) => f.debug_tuple("DriveFail").field(&fan.0).finish(),
+            FanEvent::WatchdogExpired => f.write_str("WatchdogExpired"),
+        }
+    }
+}
+
+/// Iterator over the events decoded from a single read of the status registers
+///
+/// The Watchdog Timer event is yielded first, followed by the events of each fan in ascending
+/// order.
+#[derive(Clone, Debug)]
+pub struct FanEvents {
+    faults: Faults,
+    count: u8,
+    index: u8,
+}
+
+impl FanEvents {
+    /// Number of events which can be reported for each fan
+    const EVENTS_PER_FAN: u8 = 3;
+
+    pub(crate) fn new(faults: Faults, count: u8) -> Self {
+        Self {
+            faults,
+            count,
+            index: 0,
+        }
+    }
+
+    /// The faults the events were decoded from
+    pub fn faults(&self) -> Faults {
+        self.faults
+    }
+}
+
+impl Iterator for FanEvents {
+    type Item = FanEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let total = 1 + self.count * Self::EVENTS_PER_FAN;
+
+        while self.index < total {
+            let index = self.index;
+            self.index += 1;
+
+            if index == 0 {
+                if self.faults.watchdog_expired() {
+                    return Some(FanEvent::WatchdogExpired);
+                }
+                continue;
+            }
+
+            let fan = FanSelect((index - 1) / Self::EVENTS_PER_FAN + 1);
+            let faults = self.faults.fan(fan);
+            let event = match (index - 1) % Self::EVENTS_PER_FAN {
+                0 if faults.stalled => FanEvent::Stall(fan),
+                1 if faults.spin_failed => FanEvent::SpinUpFailed(fan),
+                2 if faults.drive_failed => FanEvent::DriveFail(fan),
+                _ => continue,
+            };
+            return Some(event);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FanEvent, FanEvents};
+    use crate::{
+        registers::{FanDriveFailStatus, FanStallStatus, FanStatus},
+        Faults,
+    };
+    use std::{format, string::String, vec::Vec};
+
+    #[test]
+    fn decode_events() {
+        let mut faults = Faults::default();
+        faults.latch_status(FanStatus::from(0x80));
+        faults.latch_stall(FanStallStatus::from(0b0000_0101));
+        faults.latch_drive_fail(FanDriveFailStatus::from(0b0000_0001));
+
+        let events: Vec<String> = FanEvents::new(faults, 3)
+            .map(|event| format!("{:?}", event))
+            .collect();
+        assert_eq!(events, ["WatchdogExpired", "Stall(1)", "DriveFail(1)", "Stall(3)"]);
+
+        // Events are only reported for fans the device supports
+        let mut events = FanEvents::new(faults, 1);
+        assert!(matches!(events.nth(2), Some(FanEvent::DriveFail(fan)) if fan.0 == 1));
+        assert!(events.next().is_none());
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::FanSelect;
+
+/// A set of fans, used to apply a setting to several fans at once.
+///
+/// A set can hold fans 0 through 7. Fans outside of that range are recorded as fan 0, which is
+/// rejected by every device, so that an out of range selection is never silently dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FanSet(u8);
+
+impl FanSet {
+    /// A set containing no fans
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// A set containing fans `1..=count`
+    pub const fn first(count: u8) -> Self {
+        let count = if count > 7 { 7 } else { count };
+        Self(((1_u16 << (count + 1)) - 2) as u8)
+    }
+
+    /// Return the set with the selected fan added
+    pub fn with(mut self, sel: FanSelect) -> Self {
+        self.insert(sel);
+        self
+    }
+
+    /// Add the selected fan to the set
+    pub fn insert(&mut self, sel: FanSelect) {
+        self.0 |= Self::bit(sel);
+    }
+
+    /// Remove the selected fan from the set
+    pub fn remove(&mut self, sel: FanSelect) {
+        self.0 &= !Self::bit(sel);
+    }
+
+    /// Determine if the selected fan is part of the set
+    pub fn contains(&self, sel: FanSelect) -> bool {
+        self.0 & Self::bit(sel) != 0
+    }
+
+    /// Determine if the set contains no fans
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the fans in the set in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = FanSelect> {
+        let bits = self.0;
+        (0..8_u8)
+            .filter(move |fan| bits & (1 << fan) != 0)
+            .map(FanSelect)
+    }
+
+    fn bit(sel: FanSelect) -> u8 {
+        if sel.0 < 8 {
+            1 << sel.0
+        } else {
+            1
+        }
+    }
+}
+
+impl From<FanSelect> for FanSet {
+    fn from(sel: FanSelect) -> Self {
+        Self::empty().with(sel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FanSet;
+    use crate::FanSelect;
+    use std::vec::Vec;
+
+    #[test]
+    fn fan_set() {
+        let set = FanSet::first(5);
+        let fans: Vec<u8> = set.iter().map(|fan| fan.0).collect();
+        assert_eq!(fans, [1, 2, 3, 4, 5]);
+
+        let mut set = FanSet::empty().with(FanSelect(2)).with(FanSelect(4));
+        assert!(set.contains(FanSelect(2)));
+        assert!(!set.contains(FanSelect(3)));
+        set.remove(FanSelect(2));
+        assert_eq!(set, FanSet::from(FanSelect(4)));
+
+        // Out of range fans are kept as fan 0 so they are still rejected
+        let set = FanSet::from(FanSelect(9));
+        assert!(set.contains(FanSelect(0)));
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    registers::{FanDriveFailStatus, FanSpinStatus, FanStallStatus, FanStatus},
+    FanSelect, FanSet,
+};
+
+/// Fault conditions reported for a single fan
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FanFaults {
+    /// The TACH count exceeded the valid TACH count, indicating the fan stalled.
+    pub stalled: bool,
+
+    /// The spin-up routine failed to start the fan.
+    pub spin_failed: bool,
+
+    /// The fan cannot reach its target RPM at 100% duty cycle.
+    pub drive_failed: bool,
+}
+
+impl FanFaults {
+    /// Determine if any fault is reported for the fan
+    pub fn any(&self) -> bool {
+        self.stalled || self.spin_failed || self.drive_failed
+    }
+}
+
+/// Decoded fault report for every fan on the device
+///
+/// Each per-fan field is stored in the same layout as the status registers, where bit `n - 1`
+/// represents fan `n`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Faults {
+    stall: u8,
+    spin: u8,
+    drive_fail: u8,
+    watchdog: bool,
+}
+
+impl Faults {
+    /// Mask of the fan bits in the status registers
+    const FAN_MASK: u8 = 0x1F;
+
+    /// Faults reported for the selected fan
+    pub fn fan(&self, sel: FanSelect) -> FanFaults {
+        let bit = match sel.0 {
+            1..=5 => 1 << (sel.0 - 1),
+            _ => 0,
+        };
+
+        FanFaults {
+            stalled: self.stall & bit != 0,
+            spin_failed: self.spin & bit != 0,
+            drive_failed: self.drive_fail & bit != 0,
+        }
+    }
+
+    /// Fans which cannot reach their target RPM at 100% duty cycle
+    pub fn drive_failed(&self) -> FanSet {
+        (1..=5)
+            .map(FanSelect)
+            .filter(|sel| self.fan(*sel).drive_failed)
+            .fold(FanSet::empty(), FanSet::with)
+    }
+
+    /// Determine if the Watchdog Timer expired
+    ///
+    /// When the Watchdog Timer expires, the device drives every fan at 100% duty cycle until the
+    /// fan settings are written again.
+    pub fn watchdog_expired(&self) -> bool {
+        self.watchdog
+    }
+
+    /// Determine if no faults are reported
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Record the Watchdog Timer status from the Fan Status register
+    pub(crate) fn latch_status(&mut self, value: FanStatus) {
+        self.watchdog |= value.watch();
+    }
+
+    /// Record the stalled fans from the Fan Stall Status register
+    pub(crate) fn latch_stall(&mut self, value: FanStallStatus) {
+        self.stall |= u8::from(value) & Self::FAN_MASK;
+    }
+
+    /// Record the fans that failed to spin up from the Fan Spin Status register
+    pub(crate) fn latch_spin(&mut self, value: FanSpinStatus) {
+        self.spin |= u8::from(value) & Self::FAN_MASK;
+    }
+
+    /// Record the fans that cannot reach their target from the Fan Drive Fail Status register
+    pub(crate) fn latch_drive_fail(&mut self, value: FanDriveFailStatus) {
+        self.drive_fail |= u8::from(value) & Self::FAN_MASK;
+    }
+
+    /// Record the faults which are reported in `other`
+    pub(crate) fn merge(&mut self, other: &Faults) {
+        self.stall |= other.stall;
+        self.spin |= other.spin;
+        self.drive_fail |= other.drive_fail;
+        self.watchdog |= other.watchdog;
+    }
+
+    /// Clear the faults which are reported in `other`
+    pub(crate) fn clear(&mut self, other: &Faults) {
+        self.stall &= !other.stall;
+        self.spin &= !other.spin;
+        self.drive_fail &= !other.drive_fail;
+        self.watchdog &= !other.watchdog;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Faults;
+    use crate::{
+        registers::{FanDriveFailStatus, FanStallStatus, FanStatus},
+        FanSelect,
+    };
+
+    #[test]
+    fn latch_and_clear() {
+        let mut latched = Faults::default();
+        latched.latch_stall(FanStallStatus::from(0b0000_0001));
+        latched.latch_drive_fail(FanDriveFailStatus::from(0b0000_0100));
+        latched.latch_status(FanStatus::from(0x80));
+
+        // Cleared-on-read bits stay latched after the register reads back as zero
+        latched.latch_stall(FanStallStatus::from(0x00));
+
+        assert!(latched.fan(FanSelect(1)).stalled);
+        assert!(latched.fan(FanSelect(3)).drive_failed);
+        assert!(!latched.fan(FanSelect(2)).any());
+        assert!(latched.watchdog_expired());
+
+        let acknowledged = latched;
+        latched.latch_stall(FanStallStatus::from(0b0000_0010));
+        latched.clear(&acknowledged);
+
+        // Faults which were latched after the acknowledged report are kept
+        assert!(!latched.fan(FanSelect(1)).stalled);
+        assert!(latched.fan(FanSelect(2)).stalled);
+        assert!(!latched.watchdog_expired());
+    }
+}
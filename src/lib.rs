@@ -19,26 +19,63 @@ extern crate alloc;
 #[cfg(not(any(feature = "sync", feature = "async")))]
 compile_error!("You should probably choose at least one of `sync` and `async` features.");
 
+#[cfg(feature = "sync")]
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "sync")]
+use embedded_hal::digital::InputPin;
+#[cfg(feature = "sync")]
+use embedded_hal::i2c::Error as _;
 #[cfg(feature = "sync")]
 use embedded_hal::i2c::ErrorType;
 #[cfg(feature = "sync")]
 use embedded_hal::i2c::I2c;
 #[cfg(feature = "async")]
+use embedded_hal_async::digital::Wait;
+#[cfg(all(feature = "async", not(feature = "sync")))]
+use embedded_hal_async::i2c::Error as _;
+#[cfg(feature = "async")]
 use embedded_hal_async::i2c::ErrorType as AsyncErrorType;
 #[cfg(feature = "async")]
 use embedded_hal_async::i2c::I2c as AsyncI2c;
 
 use core::{
     fmt::{self, Debug, Formatter},
-    future::Future,
+    marker::PhantomData,
 };
 pub use fans::{FanControl, FanDutyCycle, FanRpm, FanSelect};
 
+pub use builder::Emc230xBuilder;
+use cache::RegisterCache;
 pub use error::Error;
+pub use events::{FanEvent, FanEvents};
+pub use fan_set::FanSet;
+pub use faults::{FanFaults, Faults};
+pub use lock::{LockState, Locked, Unlocked};
+pub use output::{OutputMode, Polarity};
+pub use ramp::RampRate;
 use registers::*;
+pub use speed::FanSpeed;
+pub use spin_up::SpinUp;
+pub use telemetry::{FanTelemetry, Snapshot};
+pub use tuning::Tuning;
 
+#[macro_use]
+mod diagnostics;
+
+mod builder;
+mod cache;
 mod error;
-mod registers;
+mod events;
+mod fan_set;
+mod faults;
+mod lock;
+mod output;
+mod ramp;
+pub mod registers;
+mod speed;
+mod spin_up;
+mod telemetry;
+mod tuning;
 
 /// Default I2C address for the EMC2301 device
 pub const EMC2301_I2C_ADDR: u8 = 0b0010_1111;
@@ -46,7 +83,7 @@ pub const EMC2301_I2C_ADDR: u8 = 0b0010_1111;
 /// Simplified RPM factor for calculating RPM from raw values
 ///
 /// See Equation 4-3, page 17 of the datasheet. ((SIMPLIFIED_RPM_FACTOR * m) / COUNT)
-const _SIMPLIFIED_RPM_FACTOR: f64 = 3_932_160.0;
+const _SIMPLIFIED_RPM_FACTOR: u32 = 3_932_160;
 
 #[cfg(feature = "async")]
 /// Fetch a read-only register from the device
@@ -60,14 +97,9 @@ macro_rules! register_ro {
 }
 
 #[cfg(feature = "async")]
-/// Fetch and set a register from the device which applies to all fans
-macro_rules! register {
-    ($get:ident, $set:ident, $return_type:ty) => {
-        pub async fn $get(&mut self) -> Result<$return_type, Error> {
-            self.read_register::<$return_type>(<$return_type>::ADDRESS)
-                .await
-        }
-
+/// Set a register on the device which applies to all fans
+macro_rules! register_wo {
+    ($set:ident, $return_type:ty) => {
         pub async fn $set(&mut self, value: $return_type) -> Result<(), Error> {
             self.write_register(<$return_type>::ADDRESS, value.into())
                 .await?;
@@ -77,19 +109,39 @@ macro_rules! register {
 }
 
 #[cfg(feature = "async")]
-/// Fetch and set a register from the device which applies to a specific fan
-macro_rules! fan_register {
-    ($get:ident, $set:ident, $reg_type:ty) => {
+/// Fetch a status register from the device and record its bits in the fault latch
+macro_rules! register_latched {
+    ($get:ident, $return_type:ty, $latch:ident) => {
+        pub async fn $get(&mut self) -> Result<$return_type, Error> {
+            let value = self
+                .read_register::<$return_type>(<$return_type>::ADDRESS)
+                .await?;
+            self.latched_faults.$latch(value);
+            Ok(value)
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+/// Fetch a register from the device which applies to a specific fan
+macro_rules! fan_register_ro {
+    ($get:ident, $reg_type:ty) => {
         pub async fn $get(&mut self, sel: FanSelect) -> Result<$reg_type, Error> {
             self.valid_fan(sel)?;
-            let reg = fan_register_address(sel, <$reg_type>::OFFSET)?;
+            let reg = <$reg_type>::fan_address(sel)?;
             let value = self.read_register(reg).await?;
             Ok(value)
         }
+    };
+}
 
+#[cfg(feature = "async")]
+/// Set a register on the device which applies to a specific fan
+macro_rules! fan_register_wo {
+    ($set:ident, $reg_type:ty) => {
         pub async fn $set(&mut self, sel: FanSelect, value: $reg_type) -> Result<(), Error> {
             self.valid_fan(sel)?;
-            let reg = fan_register_address(sel, <$reg_type>::OFFSET)?;
+            let reg = <$reg_type>::fan_address(sel)?;
             self.write_register(reg, value.into()).await?;
             Ok(())
         }
@@ -98,7 +150,7 @@ macro_rules! fan_register {
 
 #[cfg(feature = "sync")]
 /// Fetch a read-only register from the device
-macro_rules! register_ro {
+macro_rules! sync_register_ro {
     ($get:ident, $return_type:ty) => {
         pub fn $get(&mut self) -> Result<$return_type, Error> {
             self.read_register::<$return_type>(<$return_type>::ADDRESS)
@@ -107,13 +159,9 @@ macro_rules! register_ro {
 }
 
 #[cfg(feature = "sync")]
-/// Fetch and set a register from the device which applies to all fans
-macro_rules! register {
-    ($get:ident, $set:ident, $return_type:ty) => {
-        pub fn $get(&mut self) -> Result<$return_type, Error> {
-            self.read_register::<$return_type>(<$return_type>::ADDRESS)
-        }
-
+/// Set a register on the device which applies to all fans
+macro_rules! sync_register_wo {
+    ($set:ident, $return_type:ty) => {
         pub fn $set(&mut self, value: $return_type) -> Result<(), Error> {
             self.write_register(<$return_type>::ADDRESS, value.into())?;
             Ok(())
@@ -122,53 +170,118 @@ macro_rules! register {
 }
 
 #[cfg(feature = "sync")]
-/// Fetch and set a register from the device which applies to a specific fan
-macro_rules! fan_register {
-    ($get:ident, $set:ident, $reg_type:ty) => {
+/// Fetch a status register from the device and record its bits in the fault latch
+macro_rules! sync_register_latched {
+    ($get:ident, $return_type:ty, $latch:ident) => {
+        pub fn $get(&mut self) -> Result<$return_type, Error> {
+            let value = self.read_register::<$return_type>(<$return_type>::ADDRESS)?;
+            self.latched_faults.$latch(value);
+            Ok(value)
+        }
+    };
+}
+
+#[cfg(feature = "sync")]
+/// Fetch a register from the device which applies to a specific fan
+macro_rules! sync_fan_register_ro {
+    ($get:ident, $reg_type:ty) => {
         pub fn $get(&mut self, sel: FanSelect) -> Result<$reg_type, Error> {
             self.valid_fan(sel)?;
-            let reg = fan_register_address(sel, <$reg_type>::OFFSET)?;
+            let reg = <$reg_type>::fan_address(sel)?;
             let value = self.read_register(reg)?;
             Ok(value)
         }
+    };
+}
 
+#[cfg(feature = "sync")]
+/// Set a register on the device which applies to a specific fan
+macro_rules! sync_fan_register_wo {
+    ($set:ident, $reg_type:ty) => {
         pub fn $set(&mut self, sel: FanSelect, value: $reg_type) -> Result<(), Error> {
             self.valid_fan(sel)?;
-            let reg = fan_register_address(sel, <$reg_type>::OFFSET)?;
+            let reg = <$reg_type>::fan_address(sel)?;
             self.write_register(reg, value.into())?;
             Ok(())
         }
     };
 }
 
-/// Manually hack rounding the value because [`core`] doesn't have `round`
-///
-/// This is a terrible practice. Is there a better way to do this?
-pub(crate) fn hacky_round(value: f64) -> u8 {
-    // Interpret the value as a u8 first to get an integer value
-    let raw = value as u8;
+#[cfg(feature = "async")]
+/// Fetch and set a register from the device which applies to all fans
+macro_rules! register {
+    ($get:ident, $set:ident, $return_type:ty) => {
+        register_ro!($get, $return_type);
+        register_wo!($set, $return_type);
+    };
+}
 
-    // Reinterpret the integer value as a f64 and compare it to the original value
-    if value - raw as f64 >= 0.5 {
-        raw + 1
-    } else {
-        raw
-    }
+#[cfg(feature = "async")]
+/// Fetch and set a register from the device which applies to a specific fan
+macro_rules! fan_register {
+    ($get:ident, $set:ident, $reg_type:ty) => {
+        fan_register_ro!($get, $reg_type);
+        fan_register_wo!($set, $reg_type);
+    };
 }
 
-/// Manually hack rounding the value because [`core`] doesn't have `round`
-///
-/// This is a terrible practice. Is there a better way to do this?
-pub(crate) fn hacky_round_u16(value: f64) -> u16 {
-    // Interpret the value as a u8 first to get an integer value
-    let raw = value as u16;
+#[cfg(feature = "sync")]
+/// Fetch and set a register from the device which applies to all fans
+macro_rules! sync_register {
+    ($get:ident, $set:ident, $return_type:ty) => {
+        sync_register_ro!($get, $return_type);
+        sync_register_wo!($set, $return_type);
+    };
+}
 
-    // Reinterpret the integer value as a f64 and compare it to the original value
-    if value - raw as f64 >= 0.5 {
-        raw + 1
-    } else {
-        raw
-    }
+#[cfg(feature = "sync")]
+/// Fetch and set a register from the device which applies to a specific fan
+macro_rules! sync_fan_register {
+    ($get:ident, $set:ident, $reg_type:ty) => {
+        sync_fan_register_ro!($get, $reg_type);
+        sync_fan_register_wo!($set, $reg_type);
+    };
+}
+
+#[cfg(feature = "async")]
+/// Log the value of a register which applies to all fans
+macro_rules! info_register {
+    ($dev:expr, $reg:tt) => {
+        let value = $dev.$reg().await?;
+        info!("{}: {}", stringify!($reg), value);
+    };
+}
+
+#[cfg(feature = "async")]
+/// Log the value of a register which applies to a specific fan
+macro_rules! info_fan_register {
+    ($dev:expr, $reg:tt, $fan:expr) => {
+        let value = $dev.$reg(FanSelect($fan)).await?;
+        info!("{}: {}", stringify!($reg), value);
+    };
+}
+
+#[cfg(feature = "sync")]
+/// Log the value of a register which applies to all fans
+macro_rules! sync_info_register {
+    ($dev:expr, $reg:tt) => {
+        let value = $dev.$reg()?;
+        info!("{}: {}", stringify!($reg), value);
+    };
+}
+
+#[cfg(feature = "sync")]
+/// Log the value of a register which applies to a specific fan
+macro_rules! sync_info_fan_register {
+    ($dev:expr, $reg:tt, $fan:expr) => {
+        let value = $dev.$reg(FanSelect($fan))?;
+        info!("{}: {}", stringify!($reg), value);
+    };
+}
+
+/// Divide two integers, rounding the quotient to the nearest integer with halves rounded up
+pub(crate) const fn div_round(numerator: u32, denominator: u32) -> u32 {
+    (numerator + denominator / 2) / denominator
 }
 
 /// An TMP468 sensor on the I2C bus `I`.
@@ -179,7 +292,7 @@ pub(crate) fn hacky_round_u16(value: f64) -> u16 {
     sync(feature = "sync", self = "Emc230x"),
     async(feature = "async", keep_self)
 )]
-pub struct AsyncEmc230x<I2C> {
+pub struct AsyncEmc230x<I2C, S = Unlocked> {
     /// I2C bus
     i2c: I2C,
 
@@ -193,36 +306,117 @@ pub struct AsyncEmc230x<I2C> {
 
     /// Configurable number of poles in a fan. Typically 2.
     poles: [u8; 5],
+
+    /// PWM output polarity of each fan, used to report the duty cycle seen by the fan
+    polarity: [Polarity; 5],
+
+    /// Faults read from the status registers which have not been acknowledged yet
+    ///
+    /// Several status bits are cleared when read, so they are accumulated here until the
+    /// application acknowledges them.
+    latched_faults: Faults,
+
+    /// Last mode commanded for each fan, re-applied when the Watchdog Timer expires
+    modes: [Option<Mode>; 5],
+
+    /// Fans which select their RPM range automatically for each target RPM
+    auto_range: FanSet,
+
+    /// TACH count at or above which each fan is reported as stopped
+    stop_count: [u16; 5],
+
+    /// Shadow copy of the read/write registers, if enabled
+    cache: Option<RegisterCache>,
+
+    /// Software lock state of the device
+    state: PhantomData<S>,
 }
 
 #[maybe_async_cfg::maybe(
     sync(feature = "sync", self = "Emc230x"),
     async(feature = "async", keep_self)
 )]
-impl<I2C> Debug for AsyncEmc230x<I2C> {
+impl<I2C, S> Debug for AsyncEmc230x<I2C, S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Emc230x")
             .field("address", &self.address)
             .field("pid", &self.pid)
             .field("poles", &self.poles)
+            .field("polarity", &self.polarity)
             .finish()
     }
 }
 
+/// Mode commanded for a fan
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// The fan is driven at a fixed duty cycle.
+    DutyCycle(FanDutyCycle),
+
+    /// The RPM control algorithm targets an RPM.
+    Rpm(FanRpm),
+
+    /// The RPM control algorithm targets a raw TACH count, which may be outside of the RPM range.
+    TachCount(u16),
+}
+
+impl From<FanControl> for Mode {
+    fn from(mode: FanControl) -> Self {
+        match mode {
+            FanControl::DutyCycle(duty) => Mode::DutyCycle(duty),
+            FanControl::Rpm(rpm) => Mode::Rpm(rpm),
+        }
+    }
+}
+
+/// An EMC230x device in either software lock state, as found when it was opened
+#[maybe_async_cfg::maybe(
+    sync(
+        feature = "sync",
+        self = "Emc230xDevice",
+        idents(AsyncEmc230x(sync = "Emc230x"))
+    ),
+    async(feature = "async", keep_self)
+)]
+#[derive(Debug)]
+pub enum AsyncEmc230xDevice<I2C> {
+    /// The configuration registers of the device can be written.
+    Unlocked(AsyncEmc230x<I2C, Unlocked>),
+
+    /// The configuration registers of the device are read-only until it is power cycled.
+    Locked(AsyncEmc230x<I2C, Locked>),
+}
+
 #[maybe_async_cfg::maybe(
     sync(
         feature = "sync",
         self = "Emc230x",
-        idents(AsyncI2c(sync = "I2c"), AsyncErrorType(sync = "ErrorType"))
+        idents(
+            AsyncI2c(sync = "I2c"),
+            AsyncErrorType(sync = "ErrorType"),
+            register_ro(sync = "sync_register_ro"),
+            register_latched(sync = "sync_register_latched"),
+            register(sync = "sync_register"),
+            fan_register_ro(sync = "sync_fan_register_ro"),
+            fan_register(sync = "sync_fan_register"),
+            info_register(sync = "sync_info_register"),
+            info_fan_register(sync = "sync_info_fan_register")
+        )
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C> {
+impl<I2C: AsyncI2c + AsyncErrorType, S: LockState> AsyncEmc230x<I2C, S> {
     /// Manufacturer ID
     const MANUFACTURER_ID: u8 = 0x5D;
 
-    /// Tachometer measurement frequency (kHz)
-    const TACH_FREQUENCY_HZ: f64 = 32_768.0;
+    /// Tachometer measurement frequency (Hz)
+    const TACH_FREQUENCY_HZ: u32 = 32_768;
+
+    /// Highest RPM the device can measure
+    const MAX_RPM: FanRpm = 16_000;
+
+    /// Largest count the 13-bit TACH registers can hold
+    const MAX_TACH_COUNT: u16 = 0x1FFF;
 
     /// Determine if the device at the specified address is an EMC230x device
     async fn is_emc230x(i2c: &mut I2C, address: u8) -> Result<ProductId, Error> {
@@ -235,41 +429,6 @@ impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C> {
         }
     }
 
-    /// Initialize a new EMC230x device at the specified address
-    pub async fn new(i2c: I2C, address: u8) -> Result<Self, Error> {
-        let mut i2c = i2c;
-        let pid = Self::is_emc230x(&mut i2c, address).await?;
-
-        // Assume 2 poles for all fans by default. This is common for most fans and is a safe default.
-        let poles = [2; 5];
-
-        // Form the device so that some defaults can be set
-        let mut dev = Self {
-            i2c,
-            address,
-            pid,
-            poles,
-        };
-
-        // Set all fan outputs to push-pull to avoid waveform distortion
-        let mut output_cfg = pwm_output_config::PwmOutputConfig::default();
-        let count = dev.count();
-        for fan in 1..=count {
-            output_cfg.push_pull(fan);
-        }
-        dev.set_pwm_output_config(output_cfg).await?;
-
-        // Set RPM range to 500 RPM for all drives to capture slower fans
-        for fan in 1..=count {
-            let mut cfg = dev.fan_configuration1(FanSelect(fan)).await?;
-            cfg.set_rngx(fan_configuration1::Range::Rpm500);
-            dev.set_fan_configuration1(FanSelect(fan), cfg).await?;
-        }
-
-        // Device is configured
-        Ok(dev)
-    }
-
     /// Get the I2C address of the device
     fn address(&self) -> u8 {
         self.address
@@ -280,10 +439,15 @@ impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C> {
         self.pid.num_fans()
     }
 
+    /// Get the set of all fans the device supports
+    pub fn all_fans(&self) -> FanSet {
+        FanSet::first(self.count())
+    }
+
     /// Get the number of poles for the selected fan (used in RPM calculations)
     pub fn fan_poles(&self, sel: FanSelect) -> Result<u8, Error> {
         self.valid_fan(sel)?;
-        Ok(self.poles[sel.0 as usize])
+        Ok(self.poles[Self::fan_index(sel)])
     }
 
     /// Set the number of poles for the selected fan (used in RPM calculations)
@@ -293,12 +457,12 @@ impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C> {
     /// happen as well.
     pub fn set_fan_poles(&mut self, sel: FanSelect, poles: u8) -> Result<(), Error> {
         self.valid_fan(sel)?;
-        self.poles[sel.0 as usize] = poles;
+        self.poles[Self::fan_index(sel)] = poles;
         Ok(())
     }
 
     /// Get the tachometer frequency of the device
-    fn tach_freq(&self) -> f64 {
+    fn tach_freq(&self) -> u32 {
         Self::TACH_FREQUENCY_HZ
     }
 
@@ -306,185 +470,843 @@ impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C> {
     //     async { todo!() }
     // }
 
-    /// Set the mode of the fan
-    pub async fn set_mode(&mut self, sel: FanSelect, mode: FanControl) -> Result<(), Error> {
+    /// Get the PWM output polarity of the fan
+    pub fn polarity(&self, sel: FanSelect) -> Result<Polarity, Error> {
         self.valid_fan(sel)?;
-        let mut config = self.fan_configuration1(sel).await?;
-
-        match mode {
-            FanControl::DutyCycle(duty) => {
-                // Disable RPM mode first if it is enabled.
-                //
-                // The device appears to set the fan drive with the duty cycle corresponding to the
-                // last target RPM set. If the mode bit is set after the duty cycle, the desired
-                // duty cycle gets overwritten.
-                config.set_enagx(false);
-                self.set_fan_configuration1(sel, config).await?;
-
-                self.set_duty_cycle(sel, duty).await?;
-            }
-            FanControl::Rpm(rpm) => {
-                self.set_rpm(sel, rpm).await?;
+        Ok(self.polarity[Self::fan_index(sel)])
+    }
 
-                config.set_enagx(true);
-                self.set_fan_configuration1(sel, config).await?;
-            }
+    /// Get the PWM output driver of the fan
+    pub async fn output_mode(&mut self, sel: FanSelect) -> Result<OutputMode, Error> {
+        self.valid_fan(sel)?;
+        let value = self.pwm_output_config().await?;
+        if value.is_push_pull(sel.0) {
+            Ok(OutputMode::PushPull)
+        } else {
+            Ok(OutputMode::OpenDrain)
         }
-
-        Ok(())
     }
 
     /// Fetch the current duty cycle of the fan
+    ///
+    /// The duty cycle is reported as seen by the fan, taking an inverted output into account.
     pub async fn duty_cycle(&mut self, sel: FanSelect) -> Result<FanDutyCycle, Error> {
         self.valid_fan(sel)?;
-        let drive = self.fan_setting(sel).await?;
+        let mut drive = self.fan_setting(sel).await?;
+        if self.polarity[Self::fan_index(sel)] == Polarity::Inverted {
+            drive = drive.inverted();
+        }
         let duty = drive.duty_cycle();
         Ok(duty)
     }
 
     /// Set the duty cycle of the fan
+    ///
+    /// The duty cycle is set as seen by the fan, taking an inverted output into account. Returns
+    /// [`Error::InvalidDutyCycle`] if the duty cycle is greater than 100%.
     pub async fn set_duty_cycle(
         &mut self,
         sel: FanSelect,
         duty: FanDutyCycle,
     ) -> Result<(), Error> {
         self.valid_fan(sel)?;
-        let drive = FanDriveSetting::from_duty_cycle(duty);
+        Self::valid_duty_cycle(duty)?;
+        let mut drive = FanDriveSetting::from_duty_cycle(duty);
+        if self.polarity[Self::fan_index(sel)] == Polarity::Inverted {
+            drive = drive.inverted();
+        }
         self.set_fan_setting(sel, drive).await?;
+
+        if let Some(Mode::DutyCycle(last)) = &mut self.modes[Self::fan_index(sel)] {
+            *last = duty;
+        }
         Ok(())
     }
 
     /// Fetch the current RPM of the fan
     pub async fn rpm(&mut self, sel: FanSelect) -> Result<FanRpm, Error> {
         self.valid_fan(sel)?;
-        let raw_low = self.tach_reading_low_byte(sel).await?;
-        let raw_high = self.tach_reading_high_byte(sel).await?;
-
-        let raw = u16::from_le_bytes([raw_low.into(), raw_high.into()]) >> 3;
+        let raw = self.tach_count(sel).await?;
         let rpm = self.calc_raw_rpm(sel, raw).await?;
 
         Ok(rpm)
     }
 
-    /// Set the target RPM of the fan
-    pub async fn set_rpm(&mut self, sel: FanSelect, rpm: FanRpm) -> Result<(), Error> {
+    /// Fetch the current speed of the fan
+    ///
+    /// Unlike [`Self::rpm`], a fan which is stopped or turning too slowly to be measured is
+    /// reported as such instead of being converted to a small RPM. A fan is stopped when its TACH
+    /// count reaches the [stop count](Self::set_stop_count), and too slow when the count exceeds
+    /// the Valid TACH Count of the fan.
+    pub async fn speed(&mut self, sel: FanSelect) -> Result<FanSpeed, Error> {
         self.valid_fan(sel)?;
+        let raw = self.tach_count(sel).await?;
+
+        if raw >= self.stop_count[Self::fan_index(sel)] {
+            return Ok(FanSpeed::Stopped);
+        }
 
-        let raw = self.calc_raw_rpm(sel, rpm).await?;
-        let count = (raw << 3).to_le_bytes();
+        let valid = self.valid_tach_count(sel).await?;
+        if raw > valid.max_tach_count() {
+            return Ok(FanSpeed::BelowMeasurableRange);
+        }
 
-        self.set_tach_target_low_byte(sel, count[0].into()).await?;
-        self.set_tach_target_high_byte(sel, count[1].into()).await?;
-        Ok(())
+        let rpm = self.calc_raw_rpm(sel, raw).await?;
+        Ok(FanSpeed::Running(rpm))
     }
 
-    /// Fetch the current duty cycle and RPM of the fan
-    pub async fn report(&mut self, sel: FanSelect) -> Result<(FanDutyCycle, FanRpm), Error> {
+    /// Read the state of every fan the device supports
+    ///
+    /// The status registers are read in one transaction, followed by one transaction reading all
+    /// registers of each fan, so a snapshot of an EMC2305 takes six transactions. Faults are
+    /// latched and acknowledged the same way as [`Self::faults`].
+    pub async fn snapshot(&mut self) -> Result<Snapshot, Error> {
+        self.read_status().await?;
+
+        let mut fans = [FanTelemetry::default(); 5];
+        for fan in 1..=self.count() {
+            let sel = FanSelect(fan);
+            fans[Self::fan_index(sel)] = self.fan_telemetry(sel).await?;
+        }
+        Ok(Snapshot::new(fans, self.count()))
+    }
+
+    /// Get the RPM below which the fan is treated as stalled
+    ///
+    /// The Valid TACH Count is converted using the poles, edges and range of the fan.
+    pub async fn min_valid_rpm(&mut self, sel: FanSelect) -> Result<FanRpm, Error> {
         self.valid_fan(sel)?;
-        let duty = self.duty_cycle(sel).await?;
-        let rpm = self.rpm(sel).await?;
-        Ok((duty, rpm))
+        let valid = self.valid_tach_count(sel).await?;
+        self.calc_raw_rpm(sel, valid.max_tach_count()).await
     }
 
-    /// Minimum configured duty cycle the fan will run at.
-    pub async fn min_duty(&mut self, sel: FanSelect) -> Result<FanDutyCycle, Error> {
+    /// Get the 13-bit count of the TACH Reading of the fan
+    ///
+    /// Both bytes are read in one transaction starting at the high byte. Reading the high byte
+    /// latches the low byte, so the count cannot change between the two.
+    pub async fn tach_count(&mut self, sel: FanSelect) -> Result<u16, Error> {
         self.valid_fan(sel)?;
-        let drive = self.minimum_drive(sel).await?;
-        Ok(drive.duty_cycle())
+        let mut data = [0; 2];
+        self.read_registers(TachReadingHigh::fan_address(sel)?, &mut data)
+            .await?;
+        Ok(u16::from_be_bytes(data) >> 3)
     }
 
-    /// Set the minimum duty cycle the fan will run at.
-    pub async fn set_min_duty(&mut self, sel: FanSelect, duty: FanDutyCycle) -> Result<(), Error> {
+    /// Get the 13-bit count of the TACH Target of the fan
+    ///
+    /// Both bytes are read in one transaction.
+    pub async fn tach_target(&mut self, sel: FanSelect) -> Result<u16, Error> {
         self.valid_fan(sel)?;
-        let drive = FanMinimumDrive::from_duty_cycle(duty);
-        self.set_minimum_drive(sel, drive).await?;
-        Ok(())
+        let mut data = [0; 2];
+        self.read_registers(TachTargetLow::fan_address(sel)?, &mut data)
+            .await?;
+        Ok(u16::from_le_bytes(data) >> 3)
     }
 
-    /// Calculate either the RPM or raw value of the RPM based on the input value.
-    async fn calc_raw_rpm(&mut self, sel: FanSelect, value: u16) -> Result<u16, Error> {
-        let cfg = self.fan_configuration1(sel).await?;
+    /// Get the TACH Target of the fan in RPM
+    ///
+    /// The count is converted using the poles, edges and range of the fan.
+    pub async fn tach_target_rpm(&mut self, sel: FanSelect) -> Result<FanRpm, Error> {
+        self.valid_fan(sel)?;
+        let count = self.tach_target(sel).await?;
+        self.calc_raw_rpm(sel, count).await
+    }
 
-        let poles = self.fan_poles(sel)? as f64;
-        let n = cfg.edgx().num_edges() as f64;
-        let m = cfg.rngx().tach_count_multiplier() as f64;
-        let f_tach = self.tach_freq();
+    /// Set the 13-bit count of the TACH Target of the fan
+    ///
+    /// Counts above 0x1FFF are limited to 0x1FFF. Unlike [`Self::set_rpm`], the count is written
+    /// as is, without selecting a range. If the fan is in RPM mode, the count is re-applied when
+    /// the Watchdog Timer expires.
+    pub async fn set_tach_target_count(&mut self, sel: FanSelect, count: u16) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let count = count.min(Self::MAX_TACH_COUNT);
+        self.write_tach_target_count(sel, count).await?;
 
-        let value = ((1.0 / poles) * (n - 1.0)) / (value as f64 * (1.0 / m)) * f_tach * 60.0;
-        Ok(hacky_round_u16(value))
+        let mode = &mut self.modes[Self::fan_index(sel)];
+        if let Some(Mode::Rpm(_) | Mode::TachCount(_)) = mode {
+            *mode = Some(Mode::TachCount(count));
+        }
+        Ok(())
     }
 
-    /// Write a value to a register on the device
-    async fn write_register(&mut self, reg: u8, data: u8) -> Result<(), Error> {
-        let addr = self.address();
-        let data = [reg, data];
-        self.i2c.write(addr, &data).await.map_err(|_| Error::I2c)
+    /// Get the 13-bit count of the Drive Fail Band of the fan
+    ///
+    /// Both bytes are read in one transaction.
+    pub async fn drive_fail_band(&mut self, sel: FanSelect) -> Result<u16, Error> {
+        self.valid_fan(sel)?;
+        let mut data = [0; 2];
+        self.read_registers(DriveFailBandLow::fan_address(sel)?, &mut data)
+            .await?;
+        Ok(u16::from_le_bytes(data) >> 3)
     }
 
-    /// Read a value from a register on the device attached to the I2C bus
-    async fn raw_read<T: TryFrom<u8>>(i2c: &mut I2C, address: u8, reg: u8) -> Result<T, Error> {
-        let mut data = [0];
-        i2c.write_read(address, &[reg], data.as_mut_slice())
-            .await
-            .map_err(|_| Error::I2c)?;
+    /// Get the Drive Fail Band of the fan in RPM
+    ///
+    /// The band is a number of TACH counts above the TACH Target, so its width in RPM depends on
+    /// the target. It is converted relative to the current target using the poles, edges and
+    /// range of the fan.
+    pub async fn drive_fail_band_rpm(&mut self, sel: FanSelect) -> Result<FanRpm, Error> {
+        self.valid_fan(sel)?;
+        let cfg = self.fan_configuration1(sel).await?;
+        let target = self.tach_target(sel).await?;
+        let band = self.drive_fail_band(sel).await?;
 
-        let data: T = data[0]
-            .try_into()
-            .map_err(|_| Error::RegisterTypeConversion)?;
+        let limit = target.saturating_add(band).min(Self::MAX_TACH_COUNT);
+        let target_rpm = self.rpm_count(sel, cfg, target)?;
+        let limit_rpm = self.rpm_count(sel, cfg, limit)?;
+        Ok(target_rpm.saturating_sub(limit_rpm))
+    }
 
-        Ok(data)
+    /// Get the TACH count at or above which the fan is reported as stopped
+    pub fn stop_count(&self, sel: FanSelect) -> Result<u16, Error> {
+        self.valid_fan(sel)?;
+        Ok(self.stop_count[Self::fan_index(sel)])
     }
 
-    /// Read a value from a register on the device
-    async fn read_register<T: TryFrom<u8>>(&mut self, reg: u8) -> Result<T, Error> {
-        let addr = self.address();
-        let data = Self::raw_read(&mut self.i2c, addr, reg).await?;
-        Ok(data)
+    /// Set the TACH count at or above which the fan is reported as stopped
+    ///
+    /// Defaults to 0x1FFF, the count reported when no TACH pulses are measured. Counts above
+    /// 0x1FFF are limited to 0x1FFF.
+    pub fn set_stop_count(&mut self, sel: FanSelect, count: u16) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        self.stop_count[Self::fan_index(sel)] = count.min(Self::MAX_TACH_COUNT);
+        Ok(())
     }
 
-    /// Determine if the fan number is valid by comparing it to the number of fans the device supports.
-    fn valid_fan(&self, select: FanSelect) -> Result<(), Error> {
-        if select.0 <= self.count() && select.0 != 0 {
-            Ok(())
-        } else {
-            Err(Error::InvalidFan)
+    /// Set the target RPM of the fan
+    ///
+    /// Returns [`Error::RpmOutOfRange`] if the target cannot be represented in the RPM range of
+    /// the fan. With [automatic range selection](Self::auto_range), the range giving the best
+    /// resolution for the target is selected instead. Targets outside of every range are rejected
+    /// before the bus is accessed, and targets outside of the current range before anything is
+    /// written.
+    ///
+    /// The range is in a software locked register, so it is never written on a [`Locked`]
+    /// device. If the target needs a different range, [`Error::SoftwareLocked`] is returned
+    /// before anything is written.
+    pub async fn set_rpm(&mut self, sel: FanSelect, rpm: FanRpm) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        Self::valid_rpm(rpm)?;
+        let mut cfg = self.fan_configuration1(sel).await?;
+        self.write_rpm(sel, rpm, &mut cfg).await?;
+
+        let mode = &mut self.modes[Self::fan_index(sel)];
+        if let Some(Mode::Rpm(_) | Mode::TachCount(_)) = mode {
+            *mode = Some(Mode::Rpm(rpm));
         }
+        Ok(())
     }
 
-    /// Release the I2C bus from the device
+    /// Determine if the RPM range is selected automatically when setting a target RPM
+    pub fn auto_range(&self, sel: FanSelect) -> Result<bool, Error> {
+        self.valid_fan(sel)?;
+        Ok(self.auto_range.contains(sel))
+    }
+
+    /// Fetch the current duty cycle and RPM of the fan
+    pub async fn report(&mut self, sel: FanSelect) -> Result<(FanDutyCycle, FanRpm), Error> {
+        self.valid_fan(sel)?;
+        let duty = self.duty_cycle(sel).await?;
+        let rpm = self.rpm(sel).await?;
+        Ok((duty, rpm))
+    }
+
+    /// Minimum configured duty cycle the fan will run at.
+    ///
+    /// The value is the minimum fan drive setting, before the output polarity is applied, see
+    /// [`Self::set_min_duty`].
+    pub async fn min_duty(&mut self, sel: FanSelect) -> Result<FanDutyCycle, Error> {
+        self.valid_fan(sel)?;
+        let drive = self.minimum_drive(sel).await?;
+        Ok(drive.duty_cycle())
+    }
+
+    /// Get the spin-up routine of a fan
+    pub async fn spin_up(&mut self, sel: FanSelect) -> Result<SpinUp, Error> {
+        self.valid_fan(sel)?;
+        let value = self.spin_up_configuration(sel).await?;
+        Ok(SpinUp::from(value))
+    }
+
+    /// Get the tuning of the closed loop RPM control algorithm of a fan
+    pub async fn closed_loop_tuning(&mut self, sel: FanSelect) -> Result<Tuning, Error> {
+        self.valid_fan(sel)?;
+        let gain = self.gain(sel).await?;
+        let cfg2 = self.fan_configuration2(sel).await?;
+        let cfg1 = self.fan_configuration1(sel).await?;
+        Ok(Tuning::from_registers(gain, cfg2, cfg1))
+    }
+
+    /// Get the maximum rate at which the drive of the fan changes
+    ///
+    /// Returns `None` if Ramp Rate Control is disabled.
+    pub async fn ramp_rate(&mut self, sel: FanSelect) -> Result<Option<RampRate>, Error> {
+        self.valid_fan(sel)?;
+        if !self.fan_configuration2(sel).await?.enrcx() {
+            return Ok(None);
+        }
+
+        let step = self.max_step(sel).await?;
+        let cfg1 = self.fan_configuration1(sel).await?;
+        Ok(Some(RampRate::from_registers(step, cfg1.udtx())))
+    }
+
+    /// Get the PWM output frequency (Hz) of a fan
+    pub async fn pwm_frequency(&mut self, sel: FanSelect) -> Result<u32, Error> {
+        self.valid_fan(sel)?;
+        let base = match sel.0 {
+            1..=3 => self.pwm_base_f123().await?.base(sel.0),
+            _ => self.pwm_base_f45().await?.base(sel.0),
+        };
+        let base = base.ok_or(Error::InvalidFan)?;
+        let divide = self.pwm_divide(sel).await?;
+        Ok(base.output_hz(divide.pwm_divide()))
+    }
+
+    /// Allow the selected fans to assert the !ALERT pin when an error condition is detected
+    pub async fn enable_alert(&mut self, fans: FanSet) -> Result<(), Error> {
+        self.valid_fans(fans)?;
+        let mut value = self.interrupt_enable().await?;
+        for fan in fans.iter() {
+            value.enable(fan.0);
+        }
+        self.set_interrupt_enable(value).await
+    }
+
+    /// Prevent the selected fans from asserting the !ALERT pin
+    pub async fn disable_alert(&mut self, fans: FanSet) -> Result<(), Error> {
+        self.valid_fans(fans)?;
+        let mut value = self.interrupt_enable().await?;
+        for fan in fans.iter() {
+            value.disable(fan.0);
+        }
+        self.set_interrupt_enable(value).await
+    }
+
+    /// Determine if the !ALERT pin is blocked from asserting for all fans
+    pub async fn alert_masked(&mut self) -> Result<bool, Error> {
+        let config = self.config().await?;
+        Ok(config.mask())
+    }
+
+    /// Read all status registers and report the faults latched since they were last acknowledged
+    ///
+    /// The status registers are read in a single transaction. Faults stay reported until they are
+    /// passed to [`Self::acknowledge_faults`], even if the device has already cleared them.
+    pub async fn faults(&mut self) -> Result<Faults, Error> {
+        self.read_status().await?;
+        Ok(self.latched_faults)
+    }
+
+    /// Fetch the fans detected as aging
+    ///
+    /// A fan is aging when it cannot reach its target RPM at 100% duty cycle, as configured with
+    /// [`Self::configure_aging_detection`]. The fans stay reported until the drive fail is
+    /// acknowledged with [`Self::acknowledge_faults`].
+    pub async fn aging_fans(&mut self) -> Result<FanSet, Error> {
+        self.read_status().await?;
+        Ok(self.latched_faults.drive_failed())
+    }
+
+    /// Acknowledge a fault report returned by [`Self::faults`]
+    ///
+    /// Only the faults contained in the report are cleared, so faults latched after the report was
+    /// taken are still reported by the next call to [`Self::faults`].
+    pub fn acknowledge_faults(&mut self, faults: &Faults) {
+        self.latched_faults.clear(faults);
+    }
+
+    /// Wait for the !ALERT pin to assert and decode the events which caused it
+    ///
+    /// The pin is awaited until it is low before all status registers are read in a single
+    /// transaction. !ALERT stays asserted until the status is read, so an alert which is already
+    /// pending is reported immediately. The events are also recorded in the fault latch read by [`Self::faults`].
+    #[maybe_async_cfg::only_if(async)]
+    pub async fn wait_for_alert<P: Wait>(&mut self, pin: &mut P) -> Result<FanEvents, Error> {
+        pin.wait_for_low().await.map_err(|_| Error::AlertPin)?;
+        let faults = self.read_status().await?;
+        Ok(FanEvents::new(faults, self.count()))
+    }
+
+    /// Wait for the !ALERT pin to assert and decode the events which caused it
+    ///
+    /// The pin is polled every millisecond, sleeping with `delay` in between, until it reads low.
+    /// All status registers are then read in a single transaction. The events are also recorded
+    /// in the fault latch read by [`Self::faults`]. There is no timeout, the call blocks until the
+    /// device asserts !ALERT.
+    #[maybe_async_cfg::only_if(sync)]
+    pub fn wait_for_alert<P: InputPin, D: DelayNs>(
+        &mut self,
+        pin: &mut P,
+        delay: &mut D,
+    ) -> Result<FanEvents, Error> {
+        while pin.is_high().map_err(|_| Error::AlertPin)? {
+            delay.delay_ms(1);
+        }
+        let faults = self.read_status()?;
+        Ok(FanEvents::new(faults, self.count()))
+    }
+
+    /// Read all status registers in a single transaction and record them in the fault latch
+    ///
+    /// Returns only the faults reported by this read.
+    async fn read_status(&mut self) -> Result<Faults, Error> {
+        let mut data = [0; 4];
+        self.read_registers(FanStatus::ADDRESS, &mut data).await?;
+
+        let mut faults = Faults::default();
+        faults.latch_status(data[0].into());
+        faults.latch_stall(data[1].into());
+        faults.latch_spin(data[2].into());
+        faults.latch_drive_fail(data[3].into());
+
+        self.latched_faults.merge(&faults);
+        if faults.watchdog_expired() {
+            self.invalidate_cache();
+            self.restore_modes().await?;
+        }
+        Ok(faults)
+    }
+
+    /// Determine if the Watchdog Timer runs continuously
+    pub async fn watchdog_enabled(&mut self) -> Result<bool, Error> {
+        let config = self.config().await?;
+        Ok(config.wd_en())
+    }
+
+    /// Reset the Watchdog Timer
+    ///
+    /// The device resets the Watchdog Timer whenever a TACH Target register is written, so the
+    /// current target of the first fan is written back unchanged.
+    pub async fn kick_watchdog(&mut self) -> Result<(), Error> {
+        let sel = FanSelect(1);
+        let target = self.tach_target_high_byte(sel).await?;
+        self.set_tach_target_high_byte(sel, target).await
+    }
+
+    /// Determine if the Watchdog Timer expired since the status was last read
+    ///
+    /// If it expired, the last mode set with [`Self::set_mode`] is re-applied to each fan. Fans
+    /// which were never given a mode stay at 100% duty cycle.
+    pub async fn check_watchdog(&mut self) -> Result<bool, Error> {
+        let faults = self.read_status().await?;
+        Ok(faults.watchdog_expired())
+    }
+
+    /// Serve register reads from a shadow copy of the read/write registers
+    ///
+    /// Registers are cached the first time they are read or written, and setters write through to
+    /// the device. The status registers, TACH Readings and Fan Drive Settings are always read
+    /// from the device, as it updates them on its own. The cache is cleared when the Watchdog
+    /// Timer expires or a bus transaction fails, as the device may have been reset. Other resets,
+    /// such as a power cycle, are not detected, so [`Self::refresh_cache`] should be called after
+    /// them.
+    pub fn set_register_cache(&mut self, enabled: bool) {
+        match (enabled, &self.cache) {
+            (true, None) => self.cache = Some(RegisterCache::new()),
+            (false, _) => self.cache = None,
+            (true, Some(_)) => {}
+        }
+    }
+
+    /// Determine if register reads are served from the shadow copy of the registers
+    pub fn register_cache_enabled(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// Reload the shadow copy of the registers from the device
+    ///
+    /// Does nothing if the register cache is disabled. This should be called after the device was
+    /// reset or written by another bus master.
+    pub async fn refresh_cache(&mut self) -> Result<(), Error> {
+        let Some(cache) = &mut self.cache else {
+            return Ok(());
+        };
+        cache.invalidate();
+
+        // The status registers are cleared when read, so they are skipped
+        self.read_register::<u8>(Configuration::ADDRESS).await?;
+        let mut data = [0; 5];
+        self.read_registers(FanInterruptEnable::ADDRESS, &mut data)
+            .await?;
+
+        for fan in 1..=self.count() {
+            let sel = FanSelect(fan);
+            let mut data = [0; 13];
+            self.read_registers(PwmDivide::fan_address(sel)?, &mut data)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Read all registers of the fan in one transaction and decode its state
+    async fn fan_telemetry(&mut self, sel: FanSelect) -> Result<FanTelemetry, Error> {
+        let mut data = [0; 16];
+        self.read_registers(FanDriveSetting::fan_address(sel)?, &mut data)
+            .await?;
+        let byte = |offset: u8| data[usize::from(offset)];
+
+        let mut drive = FanDriveSetting::from(byte(FanDriveSetting::OFFSET));
+        if self.polarity[Self::fan_index(sel)] == Polarity::Inverted {
+            drive = drive.inverted();
+        }
+        let cfg = FanConfiguration1::from(byte(FanConfiguration1::OFFSET));
+        let valid = ValidTachCount::from(byte(ValidTachCount::OFFSET));
+        let target =
+            u16::from_le_bytes([byte(TachTargetLow::OFFSET), byte(TachTargetHigh::OFFSET)]) >> 3;
+        let count =
+            u16::from_be_bytes([byte(TachReadingHigh::OFFSET), byte(TachReadingLow::OFFSET)]) >> 3;
+
+        let speed = if count >= self.stop_count[Self::fan_index(sel)] {
+            FanSpeed::Stopped
+        } else if count > valid.max_tach_count() {
+            FanSpeed::BelowMeasurableRange
+        } else {
+            FanSpeed::Running(self.rpm_count(sel, cfg, count)?)
+        };
+
+        Ok(FanTelemetry {
+            duty_cycle: drive.duty_cycle(),
+            speed,
+            target_rpm: self.rpm_count(sel, cfg, target)?,
+            rpm_mode: cfg.enagx(),
+            faults: self.latched_faults.fan(sel),
+        })
+    }
+
+    /// Re-apply the last commanded mode of each fan after the Watchdog Timer expired
+    ///
+    /// Only the drive setting or TACH target is written, as the Watchdog Timer does not change
+    /// the fan configuration.
+    async fn restore_modes(&mut self) -> Result<(), Error> {
+        for fan in 1..=self.count() {
+            let sel = FanSelect(fan);
+            match self.modes[Self::fan_index(sel)] {
+                Some(Mode::DutyCycle(duty)) => self.set_duty_cycle(sel, duty).await?,
+                Some(Mode::Rpm(rpm)) => self.set_rpm(sel, rpm).await?,
+                Some(Mode::TachCount(count)) => self.write_tach_target_count(sel, count).await?,
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the TACH Target of the fan for a target RPM, switching the range of `cfg` if
+    /// automatic range selection is enabled
+    async fn write_rpm(
+        &mut self,
+        sel: FanSelect,
+        rpm: FanRpm,
+        cfg: &mut FanConfiguration1,
+    ) -> Result<(), Error> {
+        let current = cfg.rngx();
+        let range = if self.auto_range.contains(sel) {
+            // A higher range uses a larger count for the same RPM, giving a better resolution
+            (0..4)
+                .rev()
+                .map(Range::from)
+                .find(|range| self.rpm_in_range(sel, *range, cfg.edgx(), rpm))
+                .ok_or(Error::RpmOutOfRange {
+                    min: Range::Rpm500.min_rpm(),
+                    max: Self::MAX_RPM,
+                })?
+        } else {
+            self.valid_rpm_in_range(sel, *cfg, rpm)?;
+            current
+        };
+
+        // The range is in the software locked Fan Configuration 1 register. Automatic range
+        // selection is disabled by `lock`, but a locked device never writes the range.
+        if range != current && S::LOCKED {
+            return Err(Error::SoftwareLocked);
+        }
+
+        cfg.set_rngx(range);
+        let count = self.rpm_count(sel, *cfg, rpm)?;
+
+        // The target and the range are written in the order which never briefly targets a higher
+        // RPM than the old or the new target.
+        let cfg_addr = FanConfiguration1::fan_address(sel)?;
+        let raise = range.tach_count_multiplier() > current.tach_count_multiplier();
+        if range != current && !raise {
+            self.write_register(cfg_addr, (*cfg).into()).await?;
+        }
+        self.write_tach_target_count(sel, count).await?;
+        if raise {
+            self.write_register(cfg_addr, (*cfg).into()).await?;
+        }
+        Ok(())
+    }
+
+    /// Check that an RPM can be represented in the RPM range of the fan configuration `cfg`
+    fn valid_rpm_in_range(
+        &self,
+        sel: FanSelect,
+        cfg: FanConfiguration1,
+        rpm: FanRpm,
+    ) -> Result<(), Error> {
+        if self.rpm_in_range(sel, cfg.rngx(), cfg.edgx(), rpm) {
+            Ok(())
+        } else {
+            Err(Error::RpmOutOfRange {
+                min: cfg.rngx().min_rpm(),
+                max: Self::MAX_RPM,
+            })
+        }
+    }
+
+    /// Determine if a target RPM can be represented in the RPM range
+    fn rpm_in_range(&self, sel: FanSelect, range: Range, edges: Edges, rpm: FanRpm) -> bool {
+        if !(range.min_rpm()..=Self::MAX_RPM).contains(&rpm) {
+            return false;
+        }
+
+        let mut cfg = FanConfiguration1::default();
+        cfg.set_rngx(range);
+        cfg.set_edgx(edges);
+        matches!(self.rpm_count(sel, cfg, rpm), Ok(count) if count <= Self::MAX_TACH_COUNT)
+    }
+
+    /// Calculate either the RPM or raw value of the RPM based on the input value.
+    async fn calc_raw_rpm(&mut self, sel: FanSelect, value: u16) -> Result<u16, Error> {
+        let cfg = self.fan_configuration1(sel).await?;
+        self.rpm_count(sel, cfg, value)
+    }
+
+    /// Calculate either the RPM or raw value of the RPM for the fan configuration `cfg`.
+    fn rpm_count(&self, sel: FanSelect, cfg: FanConfiguration1, value: u16) -> Result<u16, Error> {
+        let poles = u32::from(self.fan_poles(sel)?);
+        let n = u32::from(cfg.edgx().num_edges());
+        let m = u32::from(cfg.rngx().tach_count_multiplier());
+        let f_tach = self.tach_freq();
+
+        // ((n - 1) / poles) / (value / m) * f_tach * 60, which stays below 2^27 before dividing
+        let numerator = (n - 1) * m * f_tach * 60;
+        let denominator = poles * u32::from(value);
+        if denominator == 0 {
+            return Ok(u16::MAX);
+        }
+
+        let value = div_round(numerator, denominator);
+        Ok(u16::try_from(value).unwrap_or(u16::MAX))
+    }
+
+    /// Write a value to a register on the device
+    async fn write_register(&mut self, reg: u8, data: u8) -> Result<(), Error> {
+        let addr = self.address();
+        let result = self.i2c.write(addr, &[reg, data]).await;
+        self.check_bus(reg, result)?;
+
+        if let Some(cache) = &mut self.cache {
+            cache.set(reg, data);
+        }
+        Ok(())
+    }
+
+    /// Read a value from a register on the device attached to the I2C bus
+    async fn raw_read<T: TryFrom<u8>>(i2c: &mut I2C, address: u8, reg: u8) -> Result<T, Error> {
+        let mut data = [0];
+        i2c.write_read(address, &[reg], data.as_mut_slice())
+            .await
+            .map_err(|e| Error::i2c(e.kind(), reg))?;
+
+        let data: T = data[0]
+            .try_into()
+            .map_err(|_| Error::RegisterTypeConversion)?;
+
+        Ok(data)
+    }
+
+    /// Write the 13-bit count of the TACH Target of the fan
+    ///
+    /// Both bytes are written in one transaction, low byte first, as the device applies the target
+    /// when the high byte is written.
+    async fn write_tach_target_count(&mut self, sel: FanSelect, count: u16) -> Result<(), Error> {
+        let data = (count << 3).to_le_bytes();
+        self.write_register_pair(TachTargetLow::fan_address(sel)?, data)
+            .await
+    }
+
+    /// Write a pair of consecutive registers in one transaction using the auto-incrementing
+    /// register pointer
+    async fn write_register_pair(&mut self, reg: u8, data: [u8; 2]) -> Result<(), Error> {
+        let addr = self.address();
+        let result = self.i2c.write(addr, &[reg, data[0], data[1]]).await;
+        self.check_bus(reg, result)?;
+
+        if let Some(cache) = &mut self.cache {
+            cache.set_all(reg, &data);
+        }
+        Ok(())
+    }
+
+    /// Read consecutive registers from the device using the auto-incrementing register pointer
+    async fn read_registers(&mut self, reg: u8, data: &mut [u8]) -> Result<(), Error> {
+        if let Some(cache) = &self.cache {
+            if cache.get_all(reg, data) {
+                return Ok(());
+            }
+        }
+
+        let addr = self.address();
+        let result = self.i2c.write_read(addr, &[reg], data).await;
+        self.check_bus(reg, result)?;
+
+        if let Some(cache) = &mut self.cache {
+            cache.set_all(reg, data);
+        }
+        Ok(())
+    }
+
+    /// Read a value from a register on the device
+    async fn read_register<T: TryFrom<u8>>(&mut self, reg: u8) -> Result<T, Error> {
+        let mut data = [0];
+        self.read_registers(reg, &mut data).await?;
+        data[0]
+            .try_into()
+            .map_err(|_| Error::RegisterTypeConversion)
+    }
+
+    /// Map the result of a bus transaction accessing `reg`, clearing the register cache if it
+    /// failed
+    fn check_bus(&mut self, reg: u8, result: Result<(), I2C::Error>) -> Result<(), Error> {
+        if result.is_err() {
+            self.invalidate_cache();
+        }
+        result.map_err(|e| Error::i2c(e.kind(), reg))
+    }
+
+    /// Forget the cached register values, if the register cache is enabled
+    fn invalidate_cache(&mut self) {
+        if let Some(cache) = &mut self.cache {
+            cache.invalidate();
+        }
+    }
+
+    /// Determine if the fan number is valid by comparing it to the number of fans the device supports.
+    fn valid_fan(&self, select: FanSelect) -> Result<(), Error> {
+        if select.0 <= self.count() && select.0 != 0 {
+            Ok(())
+        } else {
+            Err(Error::InvalidFan)
+        }
+    }
+
+    /// Determine if every fan in the set is supported by the device.
+    fn valid_fans(&self, fans: FanSet) -> Result<(), Error> {
+        fans.iter().try_for_each(|fan| self.valid_fan(fan))
+    }
+
+    /// Determine if the duty cycle is a valid percentage
+    fn valid_duty_cycle(duty: FanDutyCycle) -> Result<(), Error> {
+        if duty <= 100 {
+            Ok(())
+        } else {
+            Err(Error::InvalidDutyCycle)
+        }
+    }
+
+    /// Determine if the RPM can be represented in any RPM range of the device
+    fn valid_rpm(rpm: FanRpm) -> Result<(), Error> {
+        let min = Range::Rpm500.min_rpm();
+        if (min..=Self::MAX_RPM).contains(&rpm) {
+            Ok(())
+        } else {
+            Err(Error::RpmOutOfRange {
+                min,
+                max: Self::MAX_RPM,
+            })
+        }
+    }
+
+    /// Determine if the mode of the fan holds a valid duty cycle or RPM
+    fn valid_mode(mode: &FanControl) -> Result<(), Error> {
+        match mode {
+            FanControl::DutyCycle(duty) => Self::valid_duty_cycle(*duty),
+            FanControl::Rpm(rpm) => Self::valid_rpm(*rpm),
+        }
+    }
+
+    /// Index of the selected fan in the per-fan driver state
+    fn fan_index(sel: FanSelect) -> usize {
+        sel.0 as usize - 1
+    }
+
+    /// Output polarity of every fan configured in the PWM Polarity Config register
+    fn polarities(value: PwmPolarityConfig) -> [Polarity; 5] {
+        core::array::from_fn(|index| {
+            if value.inverted(index as u8 + 1) {
+                Polarity::Inverted
+            } else {
+                Polarity::Normal
+            }
+        })
+    }
+
+    /// Change the software lock state tracked by the driver
+    fn into_state<T: LockState>(self) -> AsyncEmc230x<I2C, T> {
+        AsyncEmc230x {
+            i2c: self.i2c,
+            address: self.address,
+            pid: self.pid,
+            poles: self.poles,
+            polarity: self.polarity,
+            latched_faults: self.latched_faults,
+            modes: self.modes,
+            auto_range: self.auto_range,
+            stop_count: self.stop_count,
+            cache: self.cache,
+            state: PhantomData,
+        }
+    }
+
+    /// Release the I2C bus from the device
     pub fn release(self) -> I2C {
         self.i2c
     }
 
     // General register access
-    register!(config, set_config, Configuration);
-    register_ro!(status, FanStatus);
-    register_ro!(stall_status, FanStallStatus);
-    register_ro!(spin_status, FanSpinStatus);
-    register_ro!(drive_fail_status, FanDriveFailStatus);
+    register_ro!(config, Configuration);
+    register_latched!(stall_status, FanStallStatus, latch_stall);
+    register_latched!(spin_status, FanSpinStatus, latch_spin);
+    register_latched!(drive_fail_status, FanDriveFailStatus, latch_drive_fail);
     register!(interrupt_enable, set_interrupt_enable, FanInterruptEnable);
-    register!(pwm_polarity_config, set_pwm_polarity_config, PwmPolarityConfig);
-    register!(pwm_output_config, set_pwm_output_config, PwmOutputConfig);
-    register!(pwm_base_f45, set_pwm_base_f45, PwmBase45);
-    register!(pwm_base_f123, set_pwm_base_f123, PwmBase123);
+    register_ro!(pwm_polarity_config, PwmPolarityConfig);
+    register_ro!(pwm_output_config, PwmOutputConfig);
+    register_ro!(pwm_base_f45, PwmBase45);
+    register_ro!(pwm_base_f123, PwmBase123);
 
     // Fan specific register access
     fan_register!(fan_setting, set_fan_setting, FanDriveSetting);
-    fan_register!(pwm_divide, set_pwm_divide, PwmDivide);
-    fan_register!(fan_configuration1, set_fan_configuration1, FanConfiguration1);
-    fan_register!(fan_configuration2, set_fan_configuration2, FanConfiguration2);
-    fan_register!(gain, set_gain, PidGain);
-    fan_register!(spin_up_configuration, set_spin_up_configuration, FanSpinUpConfig);
-    fan_register!(max_step, set_max_step, MaxStepSize);
-    fan_register!(minimum_drive, set_minimum_drive, FanMinimumDrive);
-    fan_register!(valid_tach_count, set_valid_tach_count, ValidTachCount);
-    fan_register!(drive_fail_band_low_byte, set_drive_fail_band_low_byte, DriveFailBandLow);
-    fan_register!(drive_fail_band_high_byte, set_drive_fail_band_high_byte, DriveFailBandHigh);
+    fan_register_ro!(pwm_divide, PwmDivide);
+    fan_register_ro!(fan_configuration1, FanConfiguration1);
+    fan_register_ro!(fan_configuration2, FanConfiguration2);
+    fan_register_ro!(gain, PidGain);
+    fan_register_ro!(spin_up_configuration, FanSpinUpConfig);
+    fan_register_ro!(max_step, MaxStepSize);
+    fan_register_ro!(minimum_drive, FanMinimumDrive);
+    fan_register_ro!(valid_tach_count, ValidTachCount);
+    fan_register_ro!(drive_fail_band_low_byte, DriveFailBandLow);
+    fan_register_ro!(drive_fail_band_high_byte, DriveFailBandHigh);
     fan_register!(tach_target_low_byte, set_tach_target_low_byte, TachTargetLow);
     fan_register!(tach_target_high_byte, set_tach_target_high_byte, TachTargetHigh);
     fan_register!(tach_reading_high_byte, set_tach_reading_high_byte, TachReadingHigh);
     fan_register!(tach_reading_low_byte, set_tach_reading_low_byte, TachReadingLow);
 
+    /// Fetch the Fan Status register and record its bits in the fault latch
+    ///
+    /// If the Watchdog Timer expired, the last commanded mode of each fan is re-applied.
+    pub async fn status(&mut self) -> Result<FanStatus, Error> {
+        let value: FanStatus = self.read_register(FanStatus::ADDRESS).await?;
+        self.latched_faults.latch_status(value);
+        if value.watch() {
+            self.invalidate_cache();
+            self.restore_modes().await?;
+        }
+        Ok(value)
+    }
+
     // Chip registers
     register_ro!(software_lock, SoftwareLock);
     register_ro!(product_features, ProductFeatures);
@@ -492,279 +1314,2239 @@ impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C> {
 
     /// Dump all the info and registers from the EMC230x Device
     pub async fn dump_info(&mut self) -> Result<(), Error> {
-        #[cfg(feature = "async")]
-        macro_rules! defmt_info_register {
-            ($dev:expr, $reg:tt) => {
-                let value = $dev.$reg().await?;
-                defmt::info!("{}: {:#04x}", stringify!($reg), u8::from(value));
-            };
+        let count = self.count();
+
+        info!("Address: {:#04x}", self.address());
+        info!("Fan Count: {}", count);
+
+        info_register!(self, software_lock);
+        info_register!(self, product_features);
+        info_register!(self, product_id);
+
+        info_register!(self, config);
+        info_register!(self, status);
+        info_register!(self, stall_status);
+        info_register!(self, spin_status);
+        info_register!(self, drive_fail_status);
+        info_register!(self, interrupt_enable);
+        info_register!(self, pwm_polarity_config);
+        info_register!(self, pwm_output_config);
+        info_register!(self, pwm_base_f45);
+        info_register!(self, pwm_base_f123);
+
+        for fan in 1..=count {
+            info!("Fan: {} ----------------------", fan);
+            info_fan_register!(self, fan_setting, fan);
+            info_fan_register!(self, pwm_divide, fan);
+            info_fan_register!(self, fan_configuration1, fan);
+            info_fan_register!(self, fan_configuration2, fan);
+            info_fan_register!(self, gain, fan);
+            info_fan_register!(self, spin_up_configuration, fan);
+            info_fan_register!(self, max_step, fan);
+            info_fan_register!(self, minimum_drive, fan);
+            info_fan_register!(self, valid_tach_count, fan);
+            info_fan_register!(self, drive_fail_band_low_byte, fan);
+            info_fan_register!(self, drive_fail_band_high_byte, fan);
+            info_fan_register!(self, tach_target_low_byte, fan);
+            info_fan_register!(self, tach_target_high_byte, fan);
+            info_fan_register!(self, tach_reading_high_byte, fan);
+            info_fan_register!(self, tach_reading_low_byte, fan);
+        }
+
+        Ok(())
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        feature = "sync",
+        self = "Emc230x",
+        idents(
+            AsyncI2c(sync = "I2c"),
+            AsyncErrorType(sync = "ErrorType"),
+            AsyncEmc230xDevice(sync = "Emc230xDevice"),
+            register_wo(sync = "sync_register_wo"),
+            fan_register_wo(sync = "sync_fan_register_wo")
+        )
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C: AsyncI2c + AsyncErrorType> AsyncEmc230x<I2C, Unlocked> {
+    /// Initialize a new EMC230x device at the specified address
+    ///
+    /// Every fan is configured with the defaults of [`Emc230xBuilder`]. Returns
+    /// [`Error::SoftwareLocked`] if the device is already software locked, as its configuration can
+    /// no longer be written. Use [`Self::open`] to also accept locked devices.
+    pub async fn new(i2c: I2C, address: u8) -> Result<Self, Error> {
+        Self::with_builder(i2c, address, Emc230xBuilder::new()).await
+    }
+
+    /// Initialize an EMC230x device at the specified address with the configuration of `builder`
+    ///
+    /// Fans the device does not support are rejected before anything is written. Returns
+    /// [`Error::SoftwareLocked`] if the device is already software locked.
+    pub async fn with_builder(
+        i2c: I2C,
+        address: u8,
+        builder: Emc230xBuilder,
+    ) -> Result<Self, Error> {
+        match Self::attach(i2c, address).await? {
+            AsyncEmc230xDevice::Unlocked(mut dev) => {
+                dev.configure(builder).await?;
+                Ok(dev)
+            }
+            AsyncEmc230xDevice::Locked(_) => Err(Error::SoftwareLocked),
         }
+    }
+
+    /// Initialize an EMC230x device at the specified address, which may be software locked
+    ///
+    /// An unlocked device is configured the same way as [`Self::new`]. A locked device is not
+    /// written to and is returned as [`Locked`], so only the registers which are still writable
+    /// can be changed.
+    pub async fn open(i2c: I2C, address: u8) -> Result<AsyncEmc230xDevice<I2C>, Error> {
+        let mut dev = Self::attach(i2c, address).await?;
+        if let AsyncEmc230xDevice::Unlocked(dev) = &mut dev {
+            dev.configure(Emc230xBuilder::new()).await?;
+        }
+        Ok(dev)
+    }
+
+    /// Attach to an EMC230x device at the specified address without changing its configuration
+    ///
+    /// The device is identified and its software lock and PWM polarity are read, but nothing is
+    /// written. This keeps a configuration applied earlier, for example by a bootloader. Every fan
+    /// is assumed to have 2 poles, see [`Self::set_fan_poles`].
+    pub async fn attach(i2c: I2C, address: u8) -> Result<AsyncEmc230xDevice<I2C>, Error> {
+        let mut i2c = i2c;
+        let pid = Self::is_emc230x(&mut i2c, address).await?;
+        let lock: SoftwareLock = Self::raw_read(&mut i2c, address, SoftwareLock::ADDRESS).await?;
+
+        // Assume 2 poles for all fans by default. This is common for most fans and is a safe default.
+        let poles = [2; 5];
+
+        // Keep the polarity already configured so the duty cycle is reported as the fan sees it
+        let polarity_cfg: PwmPolarityConfig =
+            Self::raw_read(&mut i2c, address, PwmPolarityConfig::ADDRESS).await?;
+        let polarity = Self::polarities(polarity_cfg);
+
+        let dev = Self {
+            i2c,
+            address,
+            pid,
+            poles,
+            polarity,
+            latched_faults: Faults::default(),
+            modes: Default::default(),
+            auto_range: FanSet::empty(),
+            stop_count: [Self::MAX_TACH_COUNT; 5],
+            cache: None,
+            state: PhantomData,
+        };
+
+        if lock.lock() {
+            Ok(AsyncEmc230xDevice::Locked(dev.into_state()))
+        } else {
+            Ok(AsyncEmc230xDevice::Unlocked(dev))
+        }
+    }
+
+    /// Apply the configuration of `builder` to the device
+    async fn configure(&mut self, builder: Emc230xBuilder) -> Result<(), Error> {
+        let Emc230xBuilder { fans, selected } = builder;
+        let count = self.count() as usize;
+
+        // Reject the configuration before anything is written
+        self.valid_fans(selected)?;
+        for init in &fans[..count] {
+            if let Some(hz) = init.pwm_frequency {
+                PwmBaseFrequencyKhz::closest(hz).ok_or(Error::InvalidPwmFrequency)?;
+            }
+            if let Some(mode) = &init.mode {
+                Self::valid_mode(mode)?;
+            }
+        }
+
+        // Set the output driver of all fans at once. Push-pull avoids waveform distortion.
+        let mut output_cfg = pwm_output_config::PwmOutputConfig::default();
+        for (sel, init) in self.all_fans().iter().zip(&fans) {
+            match init.output_mode {
+                OutputMode::PushPull => output_cfg.push_pull(sel.0),
+                OutputMode::OpenDrain => output_cfg.open_drain(sel.0),
+            }
+        }
+        self.set_pwm_output_config(output_cfg).await?;
+
+        for (sel, init) in self.all_fans().iter().zip(fans) {
+            self.poles[Self::fan_index(sel)] = init.poles;
+            self.set_auto_range(sel, init.auto_range)?;
+
+            // The default range of 500 RPM captures slower fans
+            let mut cfg = self.fan_configuration1(sel).await?;
+            cfg.set_rngx(init.range);
+            if let Some(edges) = init.edges {
+                cfg.set_edgx(edges);
+            }
+            self.set_fan_configuration1(sel, cfg).await?;
+
+            if let Some(hz) = init.pwm_frequency {
+                self.set_pwm_frequency(sel, hz).await?;
+            }
+            if let Some(mode) = init.mode {
+                self.set_mode(sel, mode).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the software lock, making the configuration registers read-only until power cycle
+    ///
+    /// The setters of the software locked registers are not available on the returned device, and
+    /// automatic RPM range selection is disabled for every fan. [`Self::set_rpm`] keeps working
+    /// for targets within the current range of the fan.
+    pub async fn lock(mut self) -> Result<AsyncEmc230x<I2C, Locked>, Error> {
+        let mut lock = SoftwareLock::from(0x00);
+        lock.set_lock(true);
+        self.write_register(SoftwareLock::ADDRESS, lock.into())
+            .await?;
+        self.auto_range = FanSet::empty();
+        Ok(self.into_state())
+    }
+
+    /// Set the mode of the fan
+    ///
+    /// The duty cycle or RPM is validated the same way as [`Self::set_duty_cycle`] and
+    /// [`Self::set_rpm`] before the mode is changed.
+    pub async fn set_mode(&mut self, sel: FanSelect, mode: FanControl) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        Self::valid_mode(&mode)?;
+        let mut config = self.fan_configuration1(sel).await?;
+
+        match mode {
+            FanControl::DutyCycle(duty) => {
+                // Disable RPM mode first if it is enabled.
+                //
+                // The device appears to set the fan drive with the duty cycle corresponding to the
+                // last target RPM set. If the mode bit is set after the duty cycle, the desired
+                // duty cycle gets overwritten.
+                config.set_enagx(false);
+                self.set_fan_configuration1(sel, config).await?;
+
+                self.set_duty_cycle(sel, duty).await?;
+            }
+            FanControl::Rpm(rpm) => {
+                self.write_rpm(sel, rpm, &mut config).await?;
+
+                config.set_enagx(true);
+                self.set_fan_configuration1(sel, config).await?;
+            }
+        }
+
+        self.modes[Self::fan_index(sel)] = Some(mode.into());
+        Ok(())
+    }
+
+    /// Select the RPM range automatically when setting a target RPM
+    ///
+    /// The range giving the best resolution for each target is selected. The selection is
+    /// disabled when the device is locked, as the range can no longer be written.
+    pub fn set_auto_range(&mut self, sel: FanSelect, enabled: bool) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        if enabled {
+            self.auto_range.insert(sel);
+        } else {
+            self.auto_range.remove(sel);
+        }
+        Ok(())
+    }
+
+    /// Set the 13-bit count of the Drive Fail Band of the fan
+    ///
+    /// Both bytes are written in one transaction, low byte first. Counts above 0x1FFF are limited
+    /// to 0x1FFF.
+    pub async fn set_drive_fail_band(&mut self, sel: FanSelect, count: u16) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let data = (count.min(Self::MAX_TACH_COUNT) << 3).to_le_bytes();
+        self.write_register_pair(DriveFailBandLow::fan_address(sel)?, data)
+            .await
+    }
+
+    /// Set the Drive Fail Band of the fan in RPM
+    ///
+    /// The band is converted to TACH counts relative to the current TACH Target using the poles,
+    /// edges and range of the fan, so it should be set after the target. Returns
+    /// [`Error::RpmOutOfRange`] if the band reaches below the lowest RPM of the range.
+    pub async fn set_drive_fail_band_rpm(
+        &mut self,
+        sel: FanSelect,
+        rpm: FanRpm,
+    ) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let cfg = self.fan_configuration1(sel).await?;
+        let target = self.tach_target(sel).await?;
+
+        let target_rpm = self.rpm_count(sel, cfg, target)?;
+        let max = target_rpm.saturating_sub(cfg.rngx().min_rpm());
+        if rpm > max {
+            return Err(Error::RpmOutOfRange { min: 0, max });
+        }
+
+        let limit_rpm = target_rpm - rpm;
+        let limit = self
+            .rpm_count(sel, cfg, limit_rpm)?
+            .min(Self::MAX_TACH_COUNT);
+        self.set_drive_fail_band(sel, limit.saturating_sub(target))
+            .await
+    }
+
+    /// Set the RPM below which the fan is treated as stalled
+    ///
+    /// The RPM is converted to the closest Valid TACH Count using the poles, edges and range of
+    /// the fan, so it should be set after those. Returns [`Error::RpmOutOfRange`] if the RPM
+    /// cannot be represented in the range of the fan.
+    pub async fn set_min_valid_rpm(&mut self, sel: FanSelect, rpm: FanRpm) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        Self::valid_rpm(rpm)?;
+        let cfg = self.fan_configuration1(sel).await?;
+        self.valid_rpm_in_range(sel, cfg, rpm)?;
+        let count = self.rpm_count(sel, cfg, rpm)?;
+        self.set_valid_tach_count(sel, ValidTachCount::from_max_tach_count(count))
+            .await
+    }
+
+    /// Configure the detection of an aging fan
+    ///
+    /// While the fan is in RPM mode, it is detected as aging when its RPM stays more than
+    /// `band_rpm` below the target for the number of update periods in `count`, even though it
+    /// is driven at 100% duty cycle. The band is converted relative to the current target, so it
+    /// should be configured after the target RPM. [`DriveFailCount::Disabled`] disables the
+    /// detection.
+    ///
+    /// Aging fans are reported by [`Self::aging_fans`].
+    pub async fn configure_aging_detection(
+        &mut self,
+        sel: FanSelect,
+        band_rpm: FanRpm,
+        count: DriveFailCount,
+    ) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        self.set_drive_fail_band_rpm(sel, band_rpm).await?;
+
+        let mut config = self.spin_up_configuration(sel).await?;
+        config.set_dfcx(count);
+        self.set_spin_up_configuration(sel, config).await
+    }
+
+    /// Set the PWM output driver of the fan
+    pub async fn set_output_mode(&mut self, sel: FanSelect, mode: OutputMode) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let mut value = self.pwm_output_config().await?;
+        match mode {
+            OutputMode::PushPull => value.push_pull(sel.0),
+            OutputMode::OpenDrain => value.open_drain(sel.0),
+        }
+        self.set_pwm_output_config(value).await?;
+        Ok(())
+    }
+
+    /// Set the PWM output polarity of the fan
+    ///
+    /// The duty cycle APIs keep working with the duty cycle seen by the fan. The current fan
+    /// drive setting is not converted, so the duty cycle should be set again afterwards.
+    pub async fn set_polarity(&mut self, sel: FanSelect, polarity: Polarity) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let mut value = self.pwm_polarity_config().await?;
+        value.set_inverted(sel.0, polarity == Polarity::Inverted);
+        self.set_pwm_polarity_config(value).await
+    }
+
+    /// Set the PWM output polarity of every fan
+    ///
+    /// The polarity used by the duty cycle APIs is updated to match the value written.
+    pub async fn set_pwm_polarity_config(&mut self, value: PwmPolarityConfig) -> Result<(), Error> {
+        self.write_register(PwmPolarityConfig::ADDRESS, value.into())
+            .await?;
+        self.polarity = Self::polarities(value);
+        Ok(())
+    }
+
+    /// Set the minimum duty cycle the fan will run at.
+    ///
+    /// The minimum applies to the fan drive setting before the output polarity. On an inverted
+    /// output it therefore limits the duty cycle seen by the fan to at most `100 - duty` percent,
+    /// so the value is not converted like the duty cycle APIs.
+    pub async fn set_min_duty(&mut self, sel: FanSelect, duty: FanDutyCycle) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        Self::valid_duty_cycle(duty)?;
+        let drive = FanMinimumDrive::from_duty_cycle(duty);
+        self.set_minimum_drive(sel, drive).await?;
+        Ok(())
+    }
+
+    /// Set the spin-up routine of a fan
+    ///
+    /// The level and duration are mapped to the nearest setting the device supports. Values
+    /// outside of the supported range are rejected with [`Error::InvalidSpinUpLevel`] or
+    /// [`Error::InvalidSpinUpTime`].
+    pub async fn set_spin_up(&mut self, sel: FanSelect, spin_up: SpinUp) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let value = spin_up.to_register()?;
+        self.set_spin_up_configuration(sel, value).await?;
+        Ok(())
+    }
+
+    /// Set the tuning of the closed loop RPM control algorithm of a fan
+    ///
+    /// The other fields of the registers, such as the RPM range and ramp rate control, are kept.
+    /// An unsupported error window is rejected with [`Error::InvalidErrorWindow`] before any
+    /// register is written.
+    pub async fn set_closed_loop_tuning(
+        &mut self,
+        sel: FanSelect,
+        tuning: Tuning,
+    ) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let mut gain = self.gain(sel).await?;
+        let mut cfg2 = self.fan_configuration2(sel).await?;
+        let mut cfg1 = self.fan_configuration1(sel).await?;
+        tuning.apply(&mut gain, &mut cfg2, &mut cfg1)?;
+
+        self.set_gain(sel, gain).await?;
+        self.set_fan_configuration2(sel, cfg2).await?;
+        self.set_fan_configuration1(sel, cfg1).await?;
+        Ok(())
+    }
+
+    /// Set the maximum rate at which the drive of the fan changes
+    ///
+    /// The rate is mapped to the closest Maximum Step and update time, which is shared with the
+    /// [closed loop tuning](Self::set_closed_loop_tuning) of the fan. `None` disables Ramp Rate
+    /// Control.
+    pub async fn set_ramp_rate(
+        &mut self,
+        sel: FanSelect,
+        rate: Option<RampRate>,
+    ) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let registers = rate.map(RampRate::to_registers).transpose()?;
+        let mut cfg2 = self.fan_configuration2(sel).await?;
+
+        if let Some((step, update)) = registers {
+            self.set_max_step(sel, step).await?;
+            let mut cfg1 = self.fan_configuration1(sel).await?;
+            cfg1.set_udtx(update);
+            self.set_fan_configuration1(sel, cfg1).await?;
+        }
+
+        cfg2.set_enrcx(registers.is_some());
+        self.set_fan_configuration2(sel, cfg2).await
+    }
+
+    /// Set the PWM output frequency (Hz) of a fan
+    ///
+    /// The base frequency and PWM Divide pair producing the closest frequency is used. Fans 1-3
+    /// and fans 4-5 share a base frequency register, but each fan has its own field, so only the
+    /// field of the selected fan is changed and the frequency of the other fans is kept.
+    pub async fn set_pwm_frequency(&mut self, sel: FanSelect, hz: u32) -> Result<(), Error> {
+        self.valid_fan(sel)?;
+        let (base, divide) = PwmBaseFrequencyKhz::closest(hz).ok_or(Error::InvalidPwmFrequency)?;
+
+        match sel.0 {
+            1..=3 => {
+                let mut value = self.pwm_base_f123().await?;
+                value.set_base(sel.0, base);
+                self.set_pwm_base_f123(value).await?;
+            }
+            _ => {
+                let mut value = self.pwm_base_f45().await?;
+                value.set_base(sel.0, base);
+                self.set_pwm_base_f45(value).await?;
+            }
+        }
+
+        self.set_pwm_divide(sel, PwmDivide::from(divide)).await?;
+        Ok(())
+    }
+
+    /// Block (`true`) or allow (`false`) the !ALERT pin from asserting for all fans
+    ///
+    /// The per-fan settings from [`Self::enable_alert`] are kept and apply again once the mask is
+    /// cleared.
+    pub async fn set_alert_mask(&mut self, masked: bool) -> Result<(), Error> {
+        let mut config = self.config().await?;
+        config.set_mask(masked);
+        self.set_config(config).await
+    }
+
+    /// Run the Watchdog Timer continuously (`true`) or only once after power-up (`false`)
+    ///
+    /// While the Watchdog Timer runs, it has to be reset with [`Self::kick_watchdog`] or by
+    /// changing a fan setting before it expires. When it expires, the device drives every fan at
+    /// 100% duty cycle.
+    pub async fn set_watchdog(&mut self, enabled: bool) -> Result<(), Error> {
+        let mut config = self.config().await?;
+        config.set_wd_en(enabled);
+        self.set_config(config).await
+    }
+
+    // Software locked register access
+    register_wo!(set_config, Configuration);
+    register_wo!(set_pwm_output_config, PwmOutputConfig);
+    register_wo!(set_pwm_base_f45, PwmBase45);
+    register_wo!(set_pwm_base_f123, PwmBase123);
+    fan_register_wo!(set_pwm_divide, PwmDivide);
+    fan_register_wo!(set_fan_configuration1, FanConfiguration1);
+    fan_register_wo!(set_fan_configuration2, FanConfiguration2);
+    fan_register_wo!(set_gain, PidGain);
+    fan_register_wo!(set_spin_up_configuration, FanSpinUpConfig);
+    fan_register_wo!(set_max_step, MaxStepSize);
+    fan_register_wo!(set_minimum_drive, FanMinimumDrive);
+    fan_register_wo!(set_valid_tach_count, ValidTachCount);
+    fan_register_wo!(set_drive_fail_band_low_byte, DriveFailBandLow);
+    fan_register_wo!(set_drive_fail_band_high_byte, DriveFailBandHigh);
+}
+
+/// Bus expectations shared by the tests of both driver flavours
+#[cfg(test)]
+mod expectations {
+    use super::*;
+    #[cfg(feature = "sync")]
+    use embedded_hal::i2c::ErrorKind;
+    #[cfg(all(feature = "async", not(feature = "sync")))]
+    use embedded_hal_async::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+    use std::{vec, vec::Vec};
+
+    use crate::registers::tach_reading::TachReading;
+
+    /// Transaction expectation builder for a [`Emc230x`] device.
+    #[derive(Clone, Debug)]
+    pub(crate) struct Emc230xExpectationBuilder {
+        address: u8,
+        _product_id: ProductId,
+        transactions: Vec<I2cTransaction>,
+    }
+
+    impl Emc230xExpectationBuilder {
+        /// Create expectations for a mock [`Emc230x`] device that has not been initialized.
+        pub(crate) fn new(address: u8, pid: ProductId) -> Self {
+            let mut transactions = vec![
+                I2cTransaction::write_read(address, vec![ManufacturerId::ADDRESS], vec![0x5D]),
+                I2cTransaction::write_read(address, vec![ProductId::ADDRESS], vec![pid.into()]),
+                I2cTransaction::write_read(
+                    address,
+                    vec![SoftwareLock::ADDRESS],
+                    vec![SoftwareLock::default().into()],
+                ),
+                I2cTransaction::write_read(
+                    address,
+                    vec![PwmPolarityConfig::ADDRESS],
+                    vec![PwmPolarityConfig::default().into()],
+                ),
+            ];
+
+            // Set the output configuration to push-pull for all fans
+            let mut output_cfg = 0x00_u8;
+            for fan in 1..=pid.num_fans() {
+                output_cfg |= 1 << (fan - 1);
+            }
+            transactions
+                .push(I2cTransaction::write(address, vec![PwmOutputConfig::ADDRESS, output_cfg]));
+
+            for fan in 1..=pid.num_fans() {
+                transactions.push(I2cTransaction::write_read(
+                    address,
+                    vec![FanConfiguration1::fan_address(FanSelect(fan))
+                        .expect("Could not set fan address")],
+                    vec![FanConfiguration1::default().into()],
+                ));
+
+                transactions.push(I2cTransaction::write(
+                    address,
+                    vec![
+                        FanConfiguration1::fan_address(FanSelect(fan))
+                            .expect("Could not set fan address"),
+                        0x0B,
+                    ],
+                ));
+            }
+
+            Self {
+                address,
+                _product_id: pid,
+                transactions,
+            }
+        }
+
+        /// Set expectations to retrieve the duty cycle of a fan.
+        pub(crate) fn duty_cycle(&mut self, select: FanSelect, duty_cycle: u8) {
+            let raw = FanDriveSetting::from_duty_cycle(duty_cycle);
+
+            self.transactions.push(I2cTransaction::write_read(
+                self.address,
+                vec![FanDriveSetting::fan_address(select).expect("Could not set fan address")],
+                vec![raw.into()],
+            ));
+        }
+
+        /// Set expectations to retrieve the RPM of a fan.
+        pub(crate) fn rpm(&mut self, select: FanSelect, rpm: u16) {
+            let mut default_cfg = FanConfiguration1::default();
+            default_cfg.set_rngx(fan_configuration1::Range::Rpm500);
+
+            let m = u32::from(default_cfg.rngx().tach_count_multiplier());
+            let raw = (_SIMPLIFIED_RPM_FACTOR * m / u32::from(rpm)) as u16;
+            let count: TachReading = TachReading::from(raw);
+
+            self.transactions.push(I2cTransaction::write_read(
+                self.address,
+                vec![TachReadingHigh::fan_address(select).expect("Could not set fan address")],
+                vec![count.raw_high(), count.raw_low()],
+            ));
+
+            self.transactions.push(I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![FanConfiguration1::fan_address(FanSelect(1))
+                    .expect("Could not set fan address")],
+                vec![default_cfg.into()],
+            ));
+        }
+
+        /// Set expectations to read a raw value from a register.
+        pub(crate) fn read(&mut self, reg: u8, value: u8) {
+            self.transactions.push(I2cTransaction::write_read(
+                self.address,
+                vec![reg],
+                vec![value],
+            ));
+        }
+
+        /// Set expectations to read consecutive registers in a single transaction.
+        pub(crate) fn read_block(&mut self, reg: u8, values: &[u8]) {
+            self.transactions.push(I2cTransaction::write_read(
+                self.address,
+                vec![reg],
+                values.to_vec(),
+            ));
+        }
+
+        /// Set expectations to write a raw value to a register.
+        pub(crate) fn write(&mut self, reg: u8, value: u8) {
+            self.transactions
+                .push(I2cTransaction::write(self.address, vec![reg, value]));
+        }
+
+        /// Set expectations to write consecutive registers in a single transaction.
+        pub(crate) fn write_block(&mut self, reg: u8, values: &[u8]) {
+            let mut data = vec![reg];
+            data.extend_from_slice(values);
+            self.transactions
+                .push(I2cTransaction::write(self.address, data));
+        }
+
+        /// Set expectations for a read of a register which fails on the bus.
+        pub(crate) fn read_error(&mut self, reg: u8, kind: ErrorKind) {
+            self.transactions.push(
+                I2cTransaction::write_read(self.address, vec![reg], vec![0]).with_error(kind),
+            );
+        }
+
+        pub(crate) fn build(self) -> Vec<I2cTransaction> {
+            self.transactions
+        }
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use embedded_hal_async::i2c::{ErrorKind, NoAcknowledgeSource};
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use std::{vec, vec::Vec};
+
+    use crate::expectations::Emc230xExpectationBuilder;
+
+    #[tokio::test]
+    async fn new() {
+        let expectations =
+            Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301).build();
+
+        let i2c = I2cMock::new(&expectations);
+        let dev = crate::AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn get_duty_cycle() {
+        let expected_duty_cycle = [100, 75, 50, 25, 0];
+
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+
+        for value in expected_duty_cycle {
+            expectations.duty_cycle(FanSelect(1), value);
+        }
+
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = crate::AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        for expected in expected_duty_cycle {
+            let result = dev
+                .duty_cycle(FanSelect(1))
+                .await
+                .expect("Could not get duty cycle");
+            assert_eq!(expected, result);
+        }
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn get_rpm() {
+        let expected_rpm: [u16; 15_500] = core::array::from_fn(|i| (i + 500) as u16);
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+
+        for value in expected_rpm {
+            expectations.rpm(FanSelect(1), value);
+        }
+
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = crate::AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        for expected in expected_rpm {
+            let result = dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
+
+            // The integer conversion matches the rounded floating point equation
+            let count = (_SIMPLIFIED_RPM_FACTOR / u32::from(expected)) as f64;
+            let reference = ((1.0 / 2.0) * 4.0 / count * 32_768.0 * 60.0).round() as u16;
+            assert_eq!(result, reference);
+
+            // Allow a ±1% margin of error due to count step size varying over different settings
+            let range = std::ops::Range {
+                start: expected as f64 * 0.99,
+                end: expected as f64 * 1.01,
+            };
+            assert!(
+                range.contains(&(result as f64)),
+                "RPM was out of expected range; Expected: {} in Range: {:?} Got: {}",
+                expected,
+                range,
+                result
+            );
+        }
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn valid_fan() {
+        let expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        let expectations = expectations.build();
+        let i2c = I2cMock::new(&expectations);
+        let dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let result = dev.valid_fan(FanSelect(1));
+        assert!(result.is_ok());
+
+        let result = dev.valid_fan(FanSelect(0));
+        assert!(result.is_err());
+
+        let result = dev.valid_fan(FanSelect(6));
+        assert!(result.is_err());
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn alert_enable() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2303);
+        expectations.read(FanInterruptEnable::ADDRESS, 0b0000_0000);
+        expectations.write(FanInterruptEnable::ADDRESS, 0b0000_0101);
+        expectations.read(FanInterruptEnable::ADDRESS, 0b0000_0101);
+        expectations.write(FanInterruptEnable::ADDRESS, 0b0000_0001);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let fans = FanSet::from(FanSelect(1)).with(FanSelect(3));
+        dev.enable_alert(fans)
+            .await
+            .expect("Could not enable alert");
+        dev.disable_alert(FanSelect(3).into())
+            .await
+            .expect("Could not disable alert");
+
+        // Fans the device does not support are rejected before touching the bus
+        let result = dev.enable_alert(FanSet::from(FanSelect(4))).await;
+        assert!(matches!(result, Err(Error::InvalidFan)));
+        let result = dev.disable_alert(FanSet::from(FanSelect(0))).await;
+        assert!(matches!(result, Err(Error::InvalidFan)));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn alert_mask() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read(Configuration::ADDRESS, 0x40);
+        expectations.write(Configuration::ADDRESS, 0xC0);
+        expectations.read(Configuration::ADDRESS, 0xC0);
+        expectations.write(Configuration::ADDRESS, 0x40);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        dev.set_alert_mask(true)
+            .await
+            .expect("Could not mask alert");
+        dev.set_alert_mask(false)
+            .await
+            .expect("Could not unmask alert");
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn faults() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2305);
+        expectations.read_block(FanStatus::ADDRESS, &[0x83, 0b0000_0001, 0b0001_0000, 0x00]);
+        expectations.read(FanDriveFailStatus::ADDRESS, 0b0000_0100);
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x00]);
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x00]);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let faults = dev.faults().await.expect("Could not read faults");
+        assert!(faults.watchdog_expired());
+        assert!(faults.fan(FanSelect(1)).stalled);
+        assert!(faults.fan(FanSelect(5)).spin_failed);
+        assert!(!faults.fan(FanSelect(2)).any());
+
+        // Raw status reads feed the same latch
+        dev.drive_fail_status()
+            .await
+            .expect("Could not read drive fail status");
+
+        // The device cleared the bits on read, but the driver keeps reporting them
+        let latched = dev.faults().await.expect("Could not read faults");
+        assert!(latched.fan(FanSelect(1)).stalled);
+        assert!(latched.fan(FanSelect(3)).drive_failed);
+
+        dev.acknowledge_faults(&latched);
+        let faults = dev.faults().await.expect("Could not read faults");
+        assert!(faults.is_empty());
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn wait_for_alert() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2302);
+        expectations.read_block(FanStatus::ADDRESS, &[0x85, 0b0000_0010, 0x00, 0b0000_0001]);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let mut pin = PinMock::new(&[PinTransaction::wait_for_state(PinState::Low)]);
+        let mut events = dev
+            .wait_for_alert(&mut pin)
+            .await
+            .expect("Could not wait for alert");
+
+        assert!(matches!(events.next(), Some(FanEvent::WatchdogExpired)));
+        assert!(matches!(events.next(), Some(FanEvent::DriveFail(fan)) if fan.0 == 1));
+        assert!(matches!(events.next(), Some(FanEvent::Stall(fan)) if fan.0 == 2));
+        assert!(events.next().is_none());
+
+        pin.done();
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn wait_for_pending_alert() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2302);
+        expectations.read_block(FanStatus::ADDRESS, &[0x01, 0b0000_0001, 0x00, 0x00]);
+        expectations.read_block(FanStatus::ADDRESS, &[0x01, 0b0000_0010, 0x00, 0x00]);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // The second stall asserts !ALERT again before the first one is handled, so the pin stays
+        // low and there is no falling edge to wait for
+        let mut pin = PinMock::new(&[
+            PinTransaction::wait_for_state(PinState::Low),
+            PinTransaction::wait_for_state(PinState::Low),
+        ]);
+        let mut events = dev
+            .wait_for_alert(&mut pin)
+            .await
+            .expect("Could not wait for alert");
+        assert!(matches!(events.next(), Some(FanEvent::Stall(fan)) if fan.0 == 1));
+        assert!(events.next().is_none());
+
+        let mut events = dev
+            .wait_for_alert(&mut pin)
+            .await
+            .expect("Could not wait for alert");
+        assert!(matches!(events.next(), Some(FanEvent::Stall(fan)) if fan.0 == 2));
+        assert!(events.next().is_none());
+
+        pin.done();
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn watchdog() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read(Configuration::ADDRESS, 0x40);
+        expectations.write(Configuration::ADDRESS, 0x60);
+        expectations.read(TachTargetHigh::FAN1_ADDRESS, 0x3D);
+        expectations.write(TachTargetHigh::FAN1_ADDRESS, 0x3D);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        dev.set_watchdog(true)
+            .await
+            .expect("Could not enable watchdog");
+        dev.kick_watchdog().await.expect("Could not kick watchdog");
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn watchdog_recovery() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2302);
+
+        // Fan 1 is driven at 50% duty cycle
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write(FanDriveSetting::FAN1_ADDRESS, 0x80);
+
+        // Fan 2 targets 2000 RPM
+        expectations.read(FanConfiguration1::FAN2_ADDRESS, 0x0B);
+        expectations.write_block(TachTargetLow::FAN2_ADDRESS, &[0x70, 0x3D]);
+        expectations.write(FanConfiguration1::FAN2_ADDRESS, 0x8B);
+
+        // No expiry, nothing is written
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x00]);
+
+        // The Watchdog Timer expired, both fans are restored
+        expectations.read_block(FanStatus::ADDRESS, &[0x80, 0x00, 0x00, 0x00]);
+        expectations.write(FanDriveSetting::FAN1_ADDRESS, 0x80);
+        expectations.read(FanConfiguration1::FAN2_ADDRESS, 0x8B);
+        expectations.write_block(TachTargetLow::FAN2_ADDRESS, &[0x70, 0x3D]);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        dev.set_mode(FanSelect(1), FanControl::DutyCycle(50))
+            .await
+            .expect("Could not set duty cycle");
+        dev.set_mode(FanSelect(2), FanControl::Rpm(2000))
+            .await
+            .expect("Could not set RPM");
+
+        let expired = dev
+            .check_watchdog()
+            .await
+            .expect("Could not check watchdog");
+        assert!(!expired);
+        let expired = dev
+            .check_watchdog()
+            .await
+            .expect("Could not check watchdog");
+        assert!(expired);
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn watchdog_recovery_tach_count() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2303);
+
+        // Fans 1 and 2 target raw counts outside of the RPM range
+        for fan in [FanSelect(1), FanSelect(2)] {
+            let cfg = FanConfiguration1::fan_address(fan).expect("Invalid fan");
+            expectations.read(cfg, 0x0B);
+            expectations
+                .write_block(TachTargetLow::fan_address(fan).expect("Invalid fan"), &[0x70, 0x3D]);
+            expectations.write(cfg, 0x8B);
+        }
+        expectations.write_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x00]);
+        expectations.write_block(TachTargetLow::FAN2_ADDRESS, &[0xF8, 0xFF]);
+
+        // Fan 3 is driven at 50% duty cycle
+        expectations.read(FanConfiguration1::FAN3_ADDRESS, 0x0B);
+        expectations.write(FanConfiguration1::FAN3_ADDRESS, 0x0B);
+        expectations.write(FanDriveSetting::FAN3_ADDRESS, 0x80);
+
+        // The Watchdog Timer expired, the counts are restored as is and every fan is restored
+        expectations.read_block(FanStatus::ADDRESS, &[0x80, 0x00, 0x00, 0x00]);
+        expectations.write_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x00]);
+        expectations.write_block(TachTargetLow::FAN2_ADDRESS, &[0xF8, 0xFF]);
+        expectations.write(FanDriveSetting::FAN3_ADDRESS, 0x80);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        for fan in [FanSelect(1), FanSelect(2)] {
+            dev.set_mode(fan, FanControl::Rpm(2000))
+                .await
+                .expect("Could not set RPM");
+        }
+        dev.set_tach_target_count(FanSelect(1), 0)
+            .await
+            .expect("Could not set target");
+        dev.set_tach_target_count(FanSelect(2), 0x1FFF)
+            .await
+            .expect("Could not set target");
+        dev.set_mode(FanSelect(3), FanControl::DutyCycle(50))
+            .await
+            .expect("Could not set duty cycle");
+
+        let expired = dev
+            .check_watchdog()
+            .await
+            .expect("Could not check watchdog");
+        assert!(expired);
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn software_lock() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.write(SoftwareLock::ADDRESS, 0x01);
+        expectations.read(Configuration::ADDRESS, 0x40);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // Software locked registers can still be read once the device is locked
+        let mut dev: AsyncEmc230x<_, Locked> = dev.lock().await.expect("Could not lock device");
+        let config = dev.config().await.expect("Could not read configuration");
+        assert_eq!(u8::from(config), 0x40);
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn open_locked() {
+        let expectations = [
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![ManufacturerId::ADDRESS], vec![0x5D]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![ProductId::ADDRESS],
+                vec![ProductId::Emc2301.into()],
+            ),
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![SoftwareLock::ADDRESS], vec![0xF9]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![PwmPolarityConfig::ADDRESS],
+                vec![0x00],
+            ),
+        ];
+
+        // A locked device is not configured when it is opened
+        let mut i2c = I2cMock::new(&expectations);
+        let dev = AsyncEmc230x::open(i2c.clone(), EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not open device");
+        assert!(matches!(dev, AsyncEmc230xDevice::Locked(_)));
+        i2c.done();
+
+        // A locked device cannot be configured by `new`
+        let mut i2c = I2cMock::new(&expectations);
+        let result = AsyncEmc230x::new(i2c.clone(), EMC2301_I2C_ADDR).await;
+        assert!(matches!(result, Err(Error::SoftwareLocked)));
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn spin_up() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.write(FanSpinUpConfig::FAN1_ADDRESS, 0b0000_1010);
+        expectations.read(FanSpinUpConfig::FAN1_ADDRESS, 0b0000_1010);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let spin_up = SpinUp {
+            level_percent: 40,
+            duration: Duration::from_secs(1),
+            kick: true,
+            drive_fail_count: DriveFailCount::Disabled,
+        };
+        dev.set_spin_up(FanSelect(1), spin_up)
+            .await
+            .expect("Could not set spin-up");
+        let value = dev
+            .spin_up(FanSelect(1))
+            .await
+            .expect("Could not get spin-up");
+        assert_eq!(value, spin_up);
+
+        // Settings the device cannot represent are rejected before touching the bus
+        let invalid = SpinUp {
+            level_percent: 20,
+            ..spin_up
+        };
+        let result = dev.set_spin_up(FanSelect(1), invalid).await;
+        assert!(matches!(result, Err(Error::InvalidSpinUpLevel)));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn closed_loop_tuning() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read(PidGain::FAN1_ADDRESS, 0x2A);
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x68);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write(PidGain::FAN1_ADDRESS, 0x19);
+        expectations.write(FanConfiguration2::FAN1_ADDRESS, 0x74);
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x0D);
+        expectations.read(PidGain::FAN1_ADDRESS, 0x19);
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x74);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0D);
+        expectations.read(PidGain::FAN1_ADDRESS, 0x19);
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x74);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0D);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // Ramp rate control, the glitch filter and the RPM range are kept
+        let tuning = Tuning {
+            kp: PidGainMultiplier::X2,
+            ki: PidGainMultiplier::X4,
+            kd: PidGainMultiplier::X2,
+            derivative: DerivativeOptions::Step,
+            error_window_rpm: 100,
+            update_time: UpdateTime::UpdateTime800ms,
+        };
+        dev.set_closed_loop_tuning(FanSelect(1), tuning)
+            .await
+            .expect("Could not set tuning");
+        let value = dev
+            .closed_loop_tuning(FanSelect(1))
+            .await
+            .expect("Could not get tuning");
+        assert_eq!(value, tuning);
+
+        // Unsupported error windows are rejected before any register is written
+        let invalid = Tuning {
+            error_window_rpm: 150,
+            ..tuning
+        };
+        let result = dev.set_closed_loop_tuning(FanSelect(1), invalid).await;
+        assert!(matches!(result, Err(Error::InvalidErrorWindow)));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn pwm_frequency() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2303);
+        expectations.read(PwmBase123::ADDRESS, 0b0011_0000);
+        expectations.write(PwmBase123::ADDRESS, 0b0011_1000);
+        expectations.write(PwmDivide::FAN2_ADDRESS, 98);
+        expectations.read(PwmBase123::ADDRESS, 0b0011_1000);
+        expectations.read(PwmDivide::FAN2_ADDRESS, 98);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // The base frequency of fan 3 is kept
+        dev.set_pwm_frequency(FanSelect(2), 50)
+            .await
+            .expect("Could not set PWM frequency");
+        let hz = dev
+            .pwm_frequency(FanSelect(2))
+            .await
+            .expect("Could not get PWM frequency");
+        assert_eq!(hz, 50);
+
+        let result = dev.set_pwm_frequency(FanSelect(2), 30_000).await;
+        assert!(matches!(result, Err(Error::InvalidPwmFrequency)));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn output_polarity() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read(PwmOutputConfig::ADDRESS, 0x01);
+        expectations.write(PwmOutputConfig::ADDRESS, 0x00);
+        expectations.read(PwmOutputConfig::ADDRESS, 0x00);
+        expectations.read(PwmPolarityConfig::ADDRESS, 0x00);
+        expectations.write(PwmPolarityConfig::ADDRESS, 0x01);
+        expectations.write(FanDriveSetting::FAN1_ADDRESS, 64);
+        expectations.read(FanDriveSetting::FAN1_ADDRESS, 64);
+        expectations.write(PwmPolarityConfig::ADDRESS, 0x00);
+        expectations.read(FanDriveSetting::FAN1_ADDRESS, 64);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        dev.set_output_mode(FanSelect(1), OutputMode::OpenDrain)
+            .await
+            .expect("Could not set output mode");
+        let mode = dev
+            .output_mode(FanSelect(1))
+            .await
+            .expect("Could not get output mode");
+        assert_eq!(mode, OutputMode::OpenDrain);
+
+        // An inverted output drives the complement of the duty cycle seen by the fan
+        dev.set_polarity(FanSelect(1), Polarity::Inverted)
+            .await
+            .expect("Could not set polarity");
+        dev.set_duty_cycle(FanSelect(1), 75)
+            .await
+            .expect("Could not set duty cycle");
+        let duty = dev
+            .duty_cycle(FanSelect(1))
+            .await
+            .expect("Could not get duty cycle");
+        assert_eq!(duty, 75);
+
+        // Writing the raw register also changes the polarity used by the duty cycle APIs
+        dev.set_pwm_polarity_config(PwmPolarityConfig::default())
+            .await
+            .expect("Could not set polarity");
+        assert_eq!(dev.polarity(FanSelect(1)), Ok(Polarity::Normal));
+        let duty = dev
+            .duty_cycle(FanSelect(1))
+            .await
+            .expect("Could not get duty cycle");
+        assert_eq!(duty, 25);
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn attach() {
+        let expectations = [
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![ManufacturerId::ADDRESS], vec![0x5D]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![ProductId::ADDRESS],
+                vec![ProductId::Emc2301.into()],
+            ),
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![SoftwareLock::ADDRESS], vec![0xF8]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![PwmPolarityConfig::ADDRESS],
+                vec![0x00],
+            ),
+        ];
+
+        // Nothing is written to the device
+        let i2c = I2cMock::new(&expectations);
+        let dev = AsyncEmc230x::attach(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not attach device");
+        let AsyncEmc230xDevice::Unlocked(dev) = dev else {
+            panic!("Device should not be locked");
+        };
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn builder() {
+        let expectations = [
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![ManufacturerId::ADDRESS], vec![0x5D]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![ProductId::ADDRESS],
+                vec![ProductId::Emc2301.into()],
+            ),
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![SoftwareLock::ADDRESS], vec![0xF8]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![PwmPolarityConfig::ADDRESS],
+                vec![0x00],
+            ),
+            I2cTransaction::write(EMC2301_I2C_ADDR, vec![PwmOutputConfig::ADDRESS, 0x00]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![FanConfiguration1::FAN1_ADDRESS],
+                vec![0x2B],
+            ),
+            I2cTransaction::write(EMC2301_I2C_ADDR, vec![FanConfiguration1::FAN1_ADDRESS, 0x43]),
+            I2cTransaction::write_read(EMC2301_I2C_ADDR, vec![PwmBase123::ADDRESS], vec![0x00]),
+            I2cTransaction::write(EMC2301_I2C_ADDR, vec![PwmBase123::ADDRESS, 0x00]),
+            I2cTransaction::write(EMC2301_I2C_ADDR, vec![PwmDivide::FAN1_ADDRESS, 26]),
+            I2cTransaction::write_read(
+                EMC2301_I2C_ADDR,
+                vec![FanConfiguration1::FAN1_ADDRESS],
+                vec![0x43],
+            ),
+            I2cTransaction::write(EMC2301_I2C_ADDR, vec![FanConfiguration1::FAN1_ADDRESS, 0x43]),
+            I2cTransaction::write(EMC2301_I2C_ADDR, vec![FanDriveSetting::FAN1_ADDRESS, 0x80]),
+        ];
+
+        let i2c = I2cMock::new(&expectations);
+        let builder = Emc230xBuilder::new()
+            .output_mode(FanSelect(1), OutputMode::OpenDrain)
+            .range(FanSelect(1), Range::Rpm2000)
+            .edges(FanSelect(1), Edges::Sample3)
+            .poles(FanSelect(1), 1)
+            .pwm_frequency(FanSelect(1), 1_000)
+            .mode(FanSelect(1), FanControl::DutyCycle(50));
+        let dev = AsyncEmc230x::with_builder(i2c, EMC2301_I2C_ADDR, builder)
+            .await
+            .expect("Could not create device");
+        assert_eq!(dev.fan_poles(FanSelect(1)).expect("Invalid fan"), 1);
+
+        let mut i2c = dev.release();
+        i2c.done();
+
+        // Fans the device does not support are rejected before anything is written
+        let mut i2c = I2cMock::new(&expectations[..4]);
+        let builder = Emc230xBuilder::new().range(FanSelect(2), Range::Rpm1000);
+        let result = AsyncEmc230x::with_builder(i2c.clone(), EMC2301_I2C_ADDR, builder).await;
+        assert!(matches!(result, Err(Error::InvalidFan)));
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn fan_poles() {
+        let expectations =
+            Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2305).build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // Every fan keeps its own poles, including the last one
+        dev.set_fan_poles(FanSelect(5), 4).expect("Invalid fan");
+        dev.set_fan_poles(FanSelect(1), 1).expect("Invalid fan");
+        assert_eq!(dev.fan_poles(FanSelect(5)).expect("Invalid fan"), 4);
+        assert_eq!(dev.fan_poles(FanSelect(1)).expect("Invalid fan"), 1);
+        assert_eq!(dev.fan_poles(FanSelect(2)).expect("Invalid fan"), 2);
+        assert!(matches!(dev.set_fan_poles(FanSelect(6), 2), Err(Error::InvalidFan)));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn auto_range() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+
+        // 700 RPM cannot be represented in the 1000 RPM range
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+
+        // 12000 RPM switches from the 1000 to the 4000 RPM range after writing the target
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+        expectations.write_block(TachTargetLow::FAN1_ADDRESS, &[0xE8, 0x51]);
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x6B);
+
+        // 700 RPM switches to the 500 RPM range before writing the target
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x6B);
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write_block(TachTargetLow::FAN1_ADDRESS, &[0x88, 0xAF]);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let result = dev.set_rpm(FanSelect(1), 700).await;
+        assert!(matches!(
+            result,
+            Err(Error::RpmOutOfRange {
+                min: 1000,
+                max: 16_000
+            })
+        ));
+
+        dev.set_auto_range(FanSelect(1), true)
+            .expect("Could not enable automatic range");
+        dev.set_rpm(FanSelect(1), 12_000)
+            .await
+            .expect("Could not set RPM");
+        dev.set_rpm(FanSelect(1), 700)
+            .await
+            .expect("Could not set RPM");
+
+        // Targets below the lowest range are rejected
+        let result = dev.set_rpm(FanSelect(1), 400).await;
+        assert!(matches!(
+            result,
+            Err(Error::RpmOutOfRange {
+                min: 500,
+                max: 16_000
+            })
+        ));
+
+        // A locked device does not switch the range, even if the selection is still enabled
+        let mut dev = dev.into_state::<Locked>();
+        let result = dev.set_rpm(FanSelect(1), 12_000).await;
+        assert!(matches!(result, Err(Error::SoftwareLocked)));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn invalid_inputs() {
+        let expectations =
+            Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301).build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // Invalid values are rejected before touching the bus
+        let result = dev.set_duty_cycle(FanSelect(1), 101).await;
+        assert!(matches!(result, Err(Error::InvalidDutyCycle)));
+        let result = dev.set_min_duty(FanSelect(1), 200).await;
+        assert!(matches!(result, Err(Error::InvalidDutyCycle)));
+        let result = dev.set_mode(FanSelect(1), FanControl::DutyCycle(150)).await;
+        assert!(matches!(result, Err(Error::InvalidDutyCycle)));
+        let result = dev.set_rpm(FanSelect(1), 0).await;
+        assert!(matches!(
+            result,
+            Err(Error::RpmOutOfRange {
+                min: 500,
+                max: 16_000
+            })
+        ));
+        let result = dev.set_mode(FanSelect(1), FanControl::Rpm(20_000)).await;
+        assert!(matches!(result, Err(Error::RpmOutOfRange { .. })));
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn speed() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+
+        // No TACH pulses
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0xFF, 0xF8]);
+
+        // Slower than the Valid TACH Count allows
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0xF6, 0x00]);
+        expectations.read(ValidTachCount::FAN1_ADDRESS, 0xF5);
+
+        // Running at 1000 RPM
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0x7A, 0xE0]);
+        expectations.read(ValidTachCount::FAN1_ADDRESS, 0xF5);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+
+        // Stopped at the configured stop count
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0x7A, 0xE0]);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let mut speeds = [FanSpeed::Stopped; 4];
+        for speed in &mut speeds[..3] {
+            *speed = dev.speed(FanSelect(1)).await.expect("Could not get speed");
+        }
+        dev.set_stop_count(FanSelect(1), 3000)
+            .expect("Could not set stop count");
+        speeds[3] = dev.speed(FanSelect(1)).await.expect("Could not get speed");
+
+        assert_eq!(
+            speeds,
+            [
+                FanSpeed::Stopped,
+                FanSpeed::BelowMeasurableRange,
+                FanSpeed::Running(1000),
+                FanSpeed::Stopped
+            ]
+        );
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    /// Register file of an EMC2301 which models the TACH latching and target update behavior
+    struct Simulator {
+        registers: [u8; 256],
+        pointer: u8,
+
+        /// TACH Reading low byte latched by reading the high byte
+        latched_low: Option<u8>,
+
+        /// Counts the TACH Reading alternates between after every transaction
+        counts: [u16; 2],
+        transactions: usize,
+
+        /// TACH Targets applied by the closed loop, on each write of the high byte
+        targets: Vec<u16>,
+    }
+
+    impl Simulator {
+        fn new() -> Self {
+            let mut registers = [0; 256];
+            registers[ManufacturerId::ADDRESS as usize] = 0x5D;
+            registers[ProductId::ADDRESS as usize] = ProductId::Emc2301.into();
+            registers[FanConfiguration1::FAN1_ADDRESS as usize] = 0x2B;
+
+            Self {
+                registers,
+                pointer: 0,
+                latched_low: None,
+                // The counts differ in both bytes, so a torn read matches neither of them
+                counts: [0x0FFF, 0x1400],
+                transactions: 0,
+                targets: Vec::new(),
+            }
+        }
+
+        fn count(&self) -> u16 {
+            self.counts[self.transactions % 2]
+        }
+
+        fn read(&mut self) -> u8 {
+            let count = self.count() << 3;
+            let value = match self.pointer {
+                reg if reg == TachReadingHigh::FAN1_ADDRESS => {
+                    self.latched_low = Some(count.to_le_bytes()[0]);
+                    count.to_le_bytes()[1]
+                }
+                reg if reg == TachReadingLow::FAN1_ADDRESS => {
+                    self.latched_low.take().unwrap_or(count.to_le_bytes()[0])
+                }
+                reg => self.registers[reg as usize],
+            };
+            self.pointer = self.pointer.wrapping_add(1);
+            value
+        }
+
+        fn write(&mut self, value: u8) {
+            self.registers[self.pointer as usize] = value;
+            if self.pointer == TachTargetHigh::FAN1_ADDRESS {
+                let low = self.registers[TachTargetLow::FAN1_ADDRESS as usize];
+                self.targets.push(u16::from_le_bytes([low, value]) >> 3);
+            }
+            self.pointer = self.pointer.wrapping_add(1);
+        }
+    }
+
+    impl embedded_hal_async::i2c::ErrorType for Simulator {
+        type Error = core::convert::Infallible;
+    }
+
+    impl embedded_hal_async::i2c::I2c for Simulator {
+        async fn transaction(
+            &mut self,
+            _address: u8,
+            operations: &mut [embedded_hal_async::i2c::Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            use embedded_hal_async::i2c::Operation;
+
+            for operation in operations {
+                match operation {
+                    Operation::Write(data) => {
+                        if let Some((reg, values)) = data.split_first() {
+                            self.pointer = *reg;
+                            values.iter().for_each(|value| self.write(*value));
+                        }
+                    }
+                    Operation::Read(buffer) => {
+                        buffer.iter_mut().for_each(|value| *value = self.read());
+                    }
+                }
+            }
+
+            self.transactions += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn tach_accessors() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+
+        // Target of 0x0F00 counts, or 2048 RPM in the 1000 RPM range with 5 edges
+        expectations.read_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
+        expectations.read_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0x78, 0x00]);
+
+        // A band of 0x0100 counts spans 2048 to 1920 RPM
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+        expectations.read_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
+        expectations.read_block(DriveFailBandLow::FAN1_ADDRESS, &[0x00, 0x08]);
+
+        // A band of 256 RPM spans 0x0F00 to 0x1125 counts
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+        expectations.read_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
+        expectations.write_block(DriveFailBandLow::FAN1_ADDRESS, &[0x28, 0x11]);
+
+        // Counts are limited to 13 bits
+        expectations.write_block(TachTargetLow::FAN1_ADDRESS, &[0xF8, 0xFF]);
+        let expectations = expectations.build();
+
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let target = dev
+            .tach_target(FanSelect(1))
+            .await
+            .expect("Could not get target");
+        assert_eq!(target, 0x0F00);
+        let target = dev
+            .tach_target_rpm(FanSelect(1))
+            .await
+            .expect("Could not get target RPM");
+        assert_eq!(target, 2048);
+        let count = dev
+            .tach_count(FanSelect(1))
+            .await
+            .expect("Could not get count");
+        assert_eq!(count, 0x0F00);
+
+        let band = dev
+            .drive_fail_band_rpm(FanSelect(1))
+            .await
+            .expect("Could not get drive fail band");
+        assert_eq!(band, 128);
+        dev.set_drive_fail_band_rpm(FanSelect(1), 256)
+            .await
+            .expect("Could not set drive fail band");
 
-        #[cfg(feature = "async")]
-        macro_rules! defmt_info_fan_register {
-            ($dev:expr, $reg:tt, $fan:expr) => {
-                let value = $dev.$reg(FanSelect($fan)).await?;
-                defmt::info!("{}: {:#04x}", stringify!($reg), u8::from(value));
-            };
-        }
-        #[cfg(feature = "sync")]
-        macro_rules! defmt_info_register {
-            ($dev:expr, $reg:tt) => {
-                let value = $dev.$reg()?;
-                defmt::info!("{}: {:#04x}", stringify!($reg), u8::from(value));
-            };
-        }
+        dev.set_tach_target_count(FanSelect(1), 0x2000)
+            .await
+            .expect("Could not set target");
 
-        #[cfg(feature = "sync")]
-        macro_rules! defmt_info_fan_register {
-            ($dev:expr, $reg:tt, $fan:expr) => {
-                let value = $dev.$reg(FanSelect($fan))?;
-                defmt::info!("{}: {:#04x}", stringify!($reg), u8::from(value));
-            };
-        }
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
-        let count = self.count();
+    #[tokio::test]
+    async fn aging_detection() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
 
-        defmt::info!("Address: {:#04x}", self.address());
-        defmt::info!("Fan Count: {}", count);
+        // A band of 256 RPM below a target of 2048 RPM, held for 32 update periods
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+        expectations.read_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
+        expectations.write_block(DriveFailBandLow::FAN1_ADDRESS, &[0x28, 0x11]);
+        expectations.read(FanSpinUpConfig::FAN1_ADDRESS, 0x19);
+        expectations.write(FanSpinUpConfig::FAN1_ADDRESS, 0x99);
+
+        // The drive fail of fan 1 stays reported after the status register is cleared
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x01]);
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x00]);
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x00]);
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x00, 0x00, 0x00]);
+        let expectations = expectations.build();
 
-        defmt_info_register!(self, software_lock);
-        defmt_info_register!(self, product_features);
-        defmt_info_register!(self, product_id);
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
 
-        defmt_info_register!(self, config);
-        defmt_info_register!(self, status);
-        defmt_info_register!(self, stall_status);
-        defmt_info_register!(self, spin_status);
-        defmt_info_register!(self, drive_fail_status);
-        defmt_info_register!(self, interrupt_enable);
-        defmt_info_register!(self, pwm_polarity_config);
-        defmt_info_register!(self, pwm_output_config);
-        defmt_info_register!(self, pwm_base_f45);
-        defmt_info_register!(self, pwm_base_f123);
+        dev.configure_aging_detection(FanSelect(1), 256, DriveFailCount::UpdatePeriod32Ms)
+            .await
+            .expect("Could not configure aging detection");
 
-        for fan in 1..=count {
-            defmt::info!("Fan: {} ----------------------", fan);
-            defmt_info_fan_register!(self, fan_setting, fan);
-            defmt_info_fan_register!(self, pwm_divide, fan);
-            defmt_info_fan_register!(self, fan_configuration1, fan);
-            defmt_info_fan_register!(self, fan_configuration2, fan);
-            defmt_info_fan_register!(self, gain, fan);
-            defmt_info_fan_register!(self, spin_up_configuration, fan);
-            defmt_info_fan_register!(self, max_step, fan);
-            defmt_info_fan_register!(self, minimum_drive, fan);
-            defmt_info_fan_register!(self, valid_tach_count, fan);
-            defmt_info_fan_register!(self, drive_fail_band_low_byte, fan);
-            defmt_info_fan_register!(self, drive_fail_band_high_byte, fan);
-            defmt_info_fan_register!(self, tach_target_low_byte, fan);
-            defmt_info_fan_register!(self, tach_target_high_byte, fan);
-            defmt_info_fan_register!(self, tach_reading_high_byte, fan);
-            defmt_info_fan_register!(self, tach_reading_low_byte, fan);
+        for _ in 0..2 {
+            let fans = dev.aging_fans().await.expect("Could not get aging fans");
+            assert_eq!(fans, FanSet::from(FanSelect(1)));
         }
 
-        Ok(())
+        let faults = dev.faults().await.expect("Could not get faults");
+        dev.acknowledge_faults(&faults);
+        let fans = dev.aging_fans().await.expect("Could not get aging fans");
+        assert!(fans.is_empty());
+
+        let mut i2c = dev.release();
+        i2c.done();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
-    use std::{vec, vec::Vec};
+    #[tokio::test]
+    async fn min_valid_rpm() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
 
-    use crate::registers::tach_reading::TachReading;
+        // 500 RPM is a count of 7864 in the 500 RPM range with 5 edges
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write(ValidTachCount::FAN1_ADDRESS, 0xF6);
 
-    /// Transaction expectation builder for a [`Emc230x`] device.
-    #[derive(Clone, Debug)]
-    struct Emc230xExpectationBuilder {
-        address: u8,
-        _product_id: ProductId,
-        transactions: Vec<I2cTransaction>,
-    }
+        // The count read back is rounded to a multiple of 32, 7872 is still 500 RPM
+        expectations.read(ValidTachCount::FAN1_ADDRESS, 0xF6);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        let expectations = expectations.build();
 
-    impl Emc230xExpectationBuilder {
-        /// Create expectations for a mock [`Emc230x`] device that has not been initialized.
-        fn new(address: u8, pid: ProductId) -> Self {
-            let mut transactions = vec![
-                I2cTransaction::write_read(address, vec![ManufacturerId::ADDRESS], vec![0x5D]),
-                I2cTransaction::write_read(address, vec![ProductId::ADDRESS], vec![pid.into()]),
-            ];
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
 
-            // Set the output configuration to push-pull for all fans
-            let mut output_cfg = 0x00_u8;
-            for fan in 1..=pid.num_fans() {
-                output_cfg |= 1 << (fan - 1);
-            }
-            transactions
-                .push(I2cTransaction::write(address, vec![PwmOutputConfig::ADDRESS, output_cfg]));
+        dev.set_min_valid_rpm(FanSelect(1), 500)
+            .await
+            .expect("Could not set minimum valid RPM");
+        let rpm = dev
+            .min_valid_rpm(FanSelect(1))
+            .await
+            .expect("Could not get minimum valid RPM");
+        assert_eq!(rpm, 500);
 
-            for fan in 1..=pid.num_fans() {
-                transactions.push(I2cTransaction::write_read(
-                    address,
-                    vec![FanConfiguration1::fan_address(FanSelect(fan))
-                        .expect("Could not set fan address")],
-                    vec![FanConfiguration1::default().into()],
-                ));
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
-                transactions.push(I2cTransaction::write(
-                    address,
-                    vec![
-                        FanConfiguration1::fan_address(FanSelect(fan))
-                            .expect("Could not set fan address"),
-                        0x0B,
-                    ],
-                ));
-            }
+    #[tokio::test]
+    async fn rpm_setters_out_of_range() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
 
-            Self {
-                address,
-                _product_id: pid,
-                transactions,
-            }
+        // 700 RPM cannot be represented in the 1000 RPM range
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+
+        // A band of 1100 RPM reaches below 1000 RPM from a target of 2048 RPM
+        for _ in 0..2 {
+            expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+            expectations.read_block(TachTargetLow::FAN1_ADDRESS, &[0x00, 0x78]);
         }
+        let expectations = expectations.build();
 
-        /// Set expectations to retrieve the duty cycle of a fan.
-        fn duty_cycle(&mut self, select: FanSelect, duty_cycle: u8) {
-            let raw = FanDriveSetting::from_duty_cycle(duty_cycle);
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
 
-            self.transactions.push(I2cTransaction::write_read(
-                self.address,
-                vec![FanDriveSetting::fan_address(select).expect("Could not set fan address")],
-                vec![raw.into()],
-            ));
-        }
+        // Nothing is written for any of the rejected values
+        let result = dev.set_min_valid_rpm(FanSelect(1), 0).await;
+        assert!(matches!(
+            result,
+            Err(Error::RpmOutOfRange {
+                min: 500,
+                max: 16_000
+            })
+        ));
+        let result = dev.set_min_valid_rpm(FanSelect(1), 700).await;
+        assert!(matches!(
+            result,
+            Err(Error::RpmOutOfRange {
+                min: 1000,
+                max: 16_000
+            })
+        ));
+
+        let result = dev.set_drive_fail_band_rpm(FanSelect(1), 1100).await;
+        assert!(matches!(result, Err(Error::RpmOutOfRange { min: 0, max: 1048 })));
+        let result = dev
+            .configure_aging_detection(FanSelect(1), 1100, DriveFailCount::UpdatePeriod32Ms)
+            .await;
+        assert!(matches!(result, Err(Error::RpmOutOfRange { min: 0, max: 1048 })));
 
-        /// Set expectations to retrieve the RPM of a fan.
-        fn rpm(&mut self, select: FanSelect, rpm: u16) {
-            let mut default_cfg = FanConfiguration1::default();
-            default_cfg.set_rngx(fan_configuration1::Range::Rpm500);
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
-            let raw = (_SIMPLIFIED_RPM_FACTOR * default_cfg.rngx().tach_count_multiplier() as f64
-                / rpm as f64) as u16;
-            let count: TachReading = TachReading::from(raw);
+    #[tokio::test]
+    async fn ramp_rate() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
 
-            self.transactions.push(I2cTransaction::write_read(
-                self.address,
-                vec![TachReadingLow::fan_address(select).expect("Could not set fan address")],
-                vec![count.raw_low()],
-            ));
+        // 10% per second is a step of 41 every 1600 ms
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x28);
+        expectations.write(MaxStepSize::FAN1_ADDRESS, 0x29);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2B);
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x2F);
+        expectations.write(FanConfiguration2::FAN1_ADDRESS, 0x68);
+
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x68);
+        expectations.read(MaxStepSize::FAN1_ADDRESS, 0x29);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x2F);
+
+        // Disabling only clears the enable bit
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x68);
+        expectations.write(FanConfiguration2::FAN1_ADDRESS, 0x28);
+        expectations.read(FanConfiguration2::FAN1_ADDRESS, 0x28);
+        let expectations = expectations.build();
 
-            self.transactions.push(I2cTransaction::write_read(
-                self.address,
-                vec![TachReadingHigh::fan_address(select).expect("Could not set fan address")],
-                vec![count.raw_high()],
-            ));
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
 
-            self.transactions.push(I2cTransaction::write_read(
-                EMC2301_I2C_ADDR,
-                vec![FanConfiguration1::fan_address(FanSelect(1))
-                    .expect("Could not set fan address")],
-                vec![default_cfg.into()],
-            ));
-        }
+        let rate = RampRate {
+            percent_per_second: 10,
+        };
+        dev.set_ramp_rate(FanSelect(1), Some(rate))
+            .await
+            .expect("Could not set ramp rate");
+        let result = dev
+            .ramp_rate(FanSelect(1))
+            .await
+            .expect("Could not get ramp rate");
+        assert_eq!(result, Some(rate));
 
-        fn build(self) -> Vec<I2cTransaction> {
-            self.transactions
-        }
+        dev.set_ramp_rate(FanSelect(1), None)
+            .await
+            .expect("Could not disable ramp rate");
+        let result = dev
+            .ramp_rate(FanSelect(1))
+            .await
+            .expect("Could not get ramp rate");
+        assert_eq!(result, None);
+
+        // Unsupported rates are rejected before the bus is accessed
+        let result = dev
+            .set_ramp_rate(
+                FanSelect(1),
+                Some(RampRate {
+                    percent_per_second: 0,
+                }),
+            )
+            .await;
+        assert!(matches!(result, Err(Error::InvalidRampRate)));
+
+        let mut i2c = dev.release();
+        i2c.done();
     }
 
     #[tokio::test]
-    async fn new() {
-        let expectations =
-            Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301).build();
+    async fn register_cache() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+
+        // The Fan Configuration 1 register is only read for the first RPM
+        expectations.rpm(FanSelect(1), 1000);
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0x7A, 0xE0]);
+
+        // Setters write through without reading the register first
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write(FanDriveSetting::FAN1_ADDRESS, 0x80);
+
+        // The Watchdog Timer expired, so the cache is cleared before the mode is re-applied
+        expectations.read_block(FanStatus::ADDRESS, &[0x80, 0x00, 0x00, 0x00]);
+        expectations.write(FanDriveSetting::FAN1_ADDRESS, 0x80);
+        expectations.rpm(FanSelect(1), 1000);
+
+        // Refreshing reloads every read/write register, skipping the status registers
+        expectations.read(Configuration::ADDRESS, 0x40);
+        expectations.read_block(FanInterruptEnable::ADDRESS, &[0x00, 0x00, 0x01, 0x00, 0x00]);
+        expectations.read_block(
+            PwmDivide::FAN1_ADDRESS,
+            &[
+                0x01, 0x0B, 0x28, 0x00, 0x2A, 0x19, 0x10, 0x66, 0xF5, 0x00, 0x00, 0xF8, 0xFF,
+            ],
+        );
+        expectations.read_block(TachReadingHigh::FAN1_ADDRESS, &[0x7A, 0xE0]);
+
+        // An expiry reported by the Fan Status register alone also clears the cache
+        expectations.read(FanStatus::ADDRESS, 0x80);
+        expectations.write(FanDriveSetting::FAN1_ADDRESS, 0x80);
+        expectations.rpm(FanSelect(1), 1000);
+        let expectations = expectations.build();
 
         let i2c = I2cMock::new(&expectations);
-        let dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR)
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
             .await
             .expect("Could not create device");
+        dev.set_register_cache(true);
+        assert!(dev.register_cache_enabled());
+
+        for _ in 0..2 {
+            let rpm = dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
+            assert_eq!(rpm, 1000);
+        }
+
+        dev.set_mode(FanSelect(1), FanControl::DutyCycle(50))
+            .await
+            .expect("Could not set mode");
+        dev.check_watchdog()
+            .await
+            .expect("Could not check watchdog");
+        dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
+
+        dev.refresh_cache().await.expect("Could not refresh cache");
+        dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
+
+        let status = dev.status().await.expect("Could not get status");
+        assert!(status.watch());
+        dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
 
         let mut i2c = dev.release();
         i2c.done();
     }
 
     #[tokio::test]
-    async fn get_duty_cycle() {
-        let expected_duty_cycle = [100, 75, 50, 25, 0];
+    async fn snapshot() {
+        let setup = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2305);
+        let mut expectations = setup.clone();
+
+        // Fan 3 stalled
+        expectations.read_block(FanStatus::ADDRESS, &[0x00, 0x04, 0x00, 0x00]);
+
+        // Fan 1 at 50% duty cycle and 1000 RPM, fan 2 targeting 2000 RPM, the others stopped
+        let block = |drive, cfg1, target: [u8; 2], reading: [u8; 2]| {
+            [
+                drive, 0x01, cfg1, 0x28, 0x00, 0x2A, 0x19, 0x10, 0x66, 0xF5, 0x00, 0x00, target[0],
+                target[1], reading[0], reading[1],
+            ]
+        };
+        let blocks = [
+            block(0x80, 0x0B, [0xF8, 0xFF], [0x7A, 0xE0]),
+            block(0xCC, 0x8B, [0x70, 0x3D], [0x3D, 0x70]),
+            block(0x00, 0x0B, [0xF8, 0xFF], [0xFF, 0xF8]),
+            block(0x00, 0x0B, [0xF8, 0xFF], [0xFF, 0xF8]),
+            block(0x00, 0x0B, [0xF8, 0xFF], [0xFF, 0xF8]),
+        ];
+        for (fan, block) in (1..=5).zip(&blocks) {
+            let reg = FanDriveSetting::fan_address(FanSelect(fan)).expect("Invalid fan");
+            expectations.read_block(reg, block);
+        }
 
-        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        let setup = setup.build();
+        let expectations = expectations.build();
+        assert_eq!(expectations.len() - setup.len(), 6);
 
-        for value in expected_duty_cycle {
-            expectations.duty_cycle(FanSelect(1), value);
-        }
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        let snapshot = dev.snapshot().await.expect("Could not take snapshot");
+        assert_eq!(snapshot.fans().len(), 5);
+        assert_eq!(
+            snapshot.fan(FanSelect(1)),
+            Some(&FanTelemetry {
+                duty_cycle: 50,
+                speed: FanSpeed::Running(1000),
+                target_rpm: 480,
+                rpm_mode: false,
+                faults: FanFaults::default(),
+            })
+        );
+        assert_eq!(
+            snapshot.fan(FanSelect(2)),
+            Some(&FanTelemetry {
+                duty_cycle: 80,
+                speed: FanSpeed::Running(2000),
+                target_rpm: 2000,
+                rpm_mode: true,
+                faults: FanFaults::default(),
+            })
+        );
+
+        let fan = snapshot.fan(FanSelect(3)).expect("Fan 3 is supported");
+        assert_eq!(fan.speed, FanSpeed::Stopped);
+        assert!(fan.faults.stalled);
+        assert!(snapshot.fan(FanSelect(6)).is_none());
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
+    #[tokio::test]
+    async fn bus_errors() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2303);
+        expectations.read_error(
+            FanConfiguration1::FAN2_ADDRESS,
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        );
+        expectations.read_error(Configuration::ADDRESS, ErrorKind::ArbitrationLoss);
         let expectations = expectations.build();
 
         let i2c = I2cMock::new(&expectations);
-        let mut dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR)
+        let mut dev = AsyncEmc230x::new(i2c, EMC2301_I2C_ADDR)
             .await
             .expect("Could not create device");
 
-        for expected in expected_duty_cycle {
-            let result = dev
-                .duty_cycle(FanSelect(1))
+        // The error of the bus is kept along with the register and fan which failed
+        let result = dev.set_mode(FanSelect(2), FanControl::DutyCycle(50)).await;
+        assert_eq!(
+            result,
+            Err(Error::I2c {
+                kind: ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+                register: FanConfiguration1::FAN2_ADDRESS,
+                fan: Some(2),
+            })
+        );
+
+        let result = dev.config().await.map(u8::from);
+        assert_eq!(
+            result,
+            Err(Error::I2c {
+                kind: ErrorKind::ArbitrationLoss,
+                register: Configuration::ADDRESS,
+                fan: None,
+            })
+        );
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
+
+    #[tokio::test]
+    async fn tear_free_counts() {
+        let mut dev = AsyncEmc230x::new(Simulator::new(), EMC2301_I2C_ADDR)
+            .await
+            .expect("Could not create device");
+
+        // The TACH Reading changes between every transaction, but is never read torn
+        let valid = [960, 768];
+        for _ in 0..8 {
+            let rpm = dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
+            assert!(valid.contains(&rpm), "Torn RPM: {}", rpm);
+        }
+
+        // Every target applied by the closed loop is a complete target
+        let rpm = [1000, 2000, 3000];
+        for value in rpm {
+            dev.set_rpm(FanSelect(1), value)
                 .await
-                .expect("Could not get duty cycle");
-            assert_eq!(expected, result);
+                .expect("Could not set RPM");
         }
+        let expected: Vec<u16> = rpm
+            .iter()
+            .map(|rpm| div_round(_SIMPLIFIED_RPM_FACTOR, u32::from(*rpm)) as u16)
+            .collect();
+
+        // The Drive Fail Band is written and read as a pair
+        dev.set_drive_fail_band(FanSelect(1), 0x0123)
+            .await
+            .expect("Could not set drive fail band");
+        let band = dev
+            .drive_fail_band(FanSelect(1))
+            .await
+            .expect("Could not get drive fail band");
+        assert_eq!(band, 0x0123);
+
+        let sim = dev.release();
+        assert_eq!(sim.targets, expected);
+    }
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod sync_tests {
+    use super::*;
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::delay::{
+        CheckedDelay as DelayMock, Transaction as DelayTransaction,
+    };
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use embedded_hal_mock::eh1::i2c::Mock as I2cMock;
+
+    use crate::expectations::Emc230xExpectationBuilder;
+
+    #[test]
+    fn new() {
+        let expectations =
+            Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301).build();
+
+        let i2c = I2cMock::new(&expectations);
+        let dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR).expect("Could not create device");
 
         let mut i2c = dev.release();
         i2c.done();
     }
 
-    #[tokio::test]
-    async fn get_rpm() {
-        let expected_rpm: [u16; 15_500] = core::array::from_fn(|i| (i + 500) as u16);
+    #[test]
+    fn duty_cycle_and_rpm() {
         let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.duty_cycle(FanSelect(1), 75);
+        expectations.rpm(FanSelect(1), 2000);
+        let expectations = expectations.build();
 
-        for value in expected_rpm {
-            expectations.rpm(FanSelect(1), value);
-        }
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR).expect("Could not create device");
+
+        let duty_cycle = dev
+            .duty_cycle(FanSelect(1))
+            .expect("Could not get duty cycle");
+        assert_eq!(duty_cycle, 75);
+        let rpm = dev.rpm(FanSelect(1)).expect("Could not get RPM");
+        assert!(rpm.abs_diff(2000) <= 1);
+
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
+    #[test]
+    fn wait_for_alert() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2302);
+        expectations.read_block(FanStatus::ADDRESS, &[0x85, 0b0000_0010, 0x00, 0b0000_0001]);
         let expectations = expectations.build();
 
         let i2c = I2cMock::new(&expectations);
-        let mut dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR)
-            .await
-            .expect("Could not create device");
+        let mut dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR).expect("Could not create device");
+
+        // The pin is polled until the device pulls it low
+        let mut pin = PinMock::new(&[
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::Low),
+        ]);
+        let mut delay =
+            DelayMock::new(&[DelayTransaction::delay_ms(1), DelayTransaction::delay_ms(1)]);
+        let mut events = dev
+            .wait_for_alert(&mut pin, &mut delay)
+            .expect("Could not wait for alert");
+
+        assert!(matches!(events.next(), Some(FanEvent::WatchdogExpired)));
+        assert!(matches!(events.next(), Some(FanEvent::DriveFail(fan)) if fan.0 == 1));
+        assert!(matches!(events.next(), Some(FanEvent::Stall(fan)) if fan.0 == 2));
+        assert!(events.next().is_none());
+
+        pin.done();
+        delay.done();
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
-        for expected in expected_rpm {
-            let result = dev.rpm(FanSelect(1)).await.expect("Could not get RPM");
+    #[test]
+    fn set_mode() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read(FanConfiguration1::FAN1_ADDRESS, 0x0B);
+        expectations.write_block(TachTargetLow::FAN1_ADDRESS, &[0x70, 0x3D]);
+        expectations.write(FanConfiguration1::FAN1_ADDRESS, 0x8B);
+        let expectations = expectations.build();
 
-            // Allow a ±1% margin of error due to count step size varying over different settings
-            let range = std::ops::Range {
-                start: expected as f64 * 0.99,
-                end: expected as f64 * 1.01,
-            };
-            assert!(
-                range.contains(&(result as f64)),
-                "RPM was out of expected range; Expected: {} in Range: {:?} Got: {}",
-                expected,
-                range,
-                result
-            );
-        }
+        let i2c = I2cMock::new(&expectations);
+        let mut dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR).expect("Could not create device");
+
+        dev.set_mode(FanSelect(1), FanControl::Rpm(2000))
+            .expect("Could not set RPM");
 
         let mut i2c = dev.release();
         i2c.done();
     }
 
-    #[tokio::test]
-    async fn valid_fan() {
-        let expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+    #[test]
+    fn bus_errors() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read_error(Configuration::ADDRESS, ErrorKind::ArbitrationLoss);
         let expectations = expectations.build();
+
         let i2c = I2cMock::new(&expectations);
-        let dev = Emc230x::new(i2c, EMC2301_I2C_ADDR)
-            .await
-            .expect("Could not create device");
+        let mut dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR).expect("Could not create device");
+
+        let result = dev.config().map(u8::from);
+        assert_eq!(
+            result,
+            Err(Error::I2c {
+                kind: ErrorKind::ArbitrationLoss,
+                register: Configuration::ADDRESS,
+                fan: None,
+            })
+        );
 
-        let result = dev.valid_fan(FanSelect(1));
-        assert!(result.is_ok());
+        let mut i2c = dev.release();
+        i2c.done();
+    }
 
-        let result = dev.valid_fan(FanSelect(0));
-        assert!(result.is_err());
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn both_flavours() {
+        let mut expectations = Emc230xExpectationBuilder::new(EMC2301_I2C_ADDR, ProductId::Emc2301);
+        expectations.read(ManufacturerId::ADDRESS, 0x5D);
+        expectations.read(ProductId::ADDRESS, ProductId::Emc2301.into());
+        expectations.read(SoftwareLock::ADDRESS, SoftwareLock::default().into());
+        expectations.read(PwmPolarityConfig::ADDRESS, PwmPolarityConfig::default().into());
+        expectations.duty_cycle(FanSelect(1), 50);
+        let expectations = expectations.build();
 
-        let result = dev.valid_fan(FanSelect(6));
-        assert!(result.is_err());
+        // Bring-up with the blocking driver, then hand the bus over to the async driver
+        let i2c = I2cMock::new(&expectations);
+        let dev = crate::Emc230x::new(i2c, EMC2301_I2C_ADDR).expect("Could not create device");
+        let i2c = dev.release();
+
+        let mut dev = match crate::AsyncEmc230x::attach(i2c, EMC2301_I2C_ADDR).await {
+            Ok(AsyncEmc230xDevice::Unlocked(dev)) => dev,
+            _ => panic!("Could not attach device"),
+        };
+        let duty_cycle = dev
+            .duty_cycle(FanSelect(1))
+            .await
+            .expect("Could not get duty cycle");
+        assert_eq!(duty_cycle, 50);
 
         let mut i2c = dev.release();
         i2c.done();
@@ -0,0 +1,150 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+mod sealed {
+    pub trait Sealed {
+        /// The software locked registers are read-only
+        const LOCKED: bool;
+    }
+}
+
+/// Software lock state of the device
+///
+/// The software locked (SWL) registers hold the configuration of the device. Once the lock is
+/// set, they are read-only until the device is power cycled. The driver tracks the lock in its
+/// type, so the setters of the SWL registers only exist while the device is [`Unlocked`].
+pub trait LockState: sealed::Sealed {}
+
+/// The software locked registers of the device can be written.
+#[derive(Clone, Copy, Debug)]
+pub struct Unlocked;
+
+/// The software locked registers of the device are read-only until it is power cycled.
+///
+/// The registers can still be read on a locked device, and are written while it is unlocked:
+///
+/// ```
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Unlocked>,
+/// # ) {
+/// dev.set_config(Configuration::default()).await.ok();
+/// dev.set_pwm_base_f123(PwmBase123::default()).await.ok();
+/// dev.set_pwm_base_f45(PwmBase45::default()).await.ok();
+/// dev.set_fan_configuration1(FanSelect(1), FanConfiguration1::default()).await.ok();
+/// dev.set_spin_up(FanSelect(1), SpinUp::from(FanSpinUpConfig::default())).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Unlocked>) {
+/// # dev.set_config(Configuration::default()).ok();
+/// # dev.set_pwm_base_f123(PwmBase123::default()).ok();
+/// # dev.set_pwm_base_f45(PwmBase45::default()).ok();
+/// # dev.set_fan_configuration1(FanSelect(1), FanConfiguration1::default()).ok();
+/// # dev.set_spin_up(FanSelect(1), SpinUp::from(FanSpinUpConfig::default())).ok();
+/// # }
+/// ```
+///
+/// ```
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Locked>,
+/// # ) {
+/// dev.config().await.ok();
+/// dev.fan_configuration1(FanSelect(1)).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Locked>) {
+/// # dev.config().ok();
+/// # dev.fan_configuration1(FanSelect(1)).ok();
+/// # }
+/// ```
+///
+/// The setters of the software locked registers do not exist on a locked device:
+///
+/// ```compile_fail
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Locked>,
+/// # ) {
+/// dev.set_config(Configuration::default()).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Locked>) {
+/// # dev.set_config(Configuration::default()).ok();
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Locked>,
+/// # ) {
+/// dev.set_pwm_base_f123(PwmBase123::default()).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Locked>) {
+/// # dev.set_pwm_base_f123(PwmBase123::default()).ok();
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Locked>,
+/// # ) {
+/// dev.set_pwm_base_f45(PwmBase45::default()).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Locked>) {
+/// # dev.set_pwm_base_f45(PwmBase45::default()).ok();
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Locked>,
+/// # ) {
+/// dev.set_fan_configuration1(FanSelect(1), FanConfiguration1::default()).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Locked>) {
+/// # dev.set_fan_configuration1(FanSelect(1), FanConfiguration1::default()).ok();
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use emc230x::{registers::*, FanSelect, Locked, SpinUp, Unlocked};
+/// # #[cfg(feature = "async")]
+/// # async fn configure<I2C: embedded_hal_async::i2c::I2c>(
+/// #     dev: &mut emc230x::AsyncEmc230x<I2C, Locked>,
+/// # ) {
+/// dev.set_spin_up(FanSelect(1), SpinUp::from(FanSpinUpConfig::default())).await.ok();
+/// # }
+/// # #[cfg(not(feature = "async"))]
+/// # fn configure<I2C: embedded_hal::i2c::I2c>(dev: &mut emc230x::Emc230x<I2C, Locked>) {
+/// # dev.set_spin_up(FanSelect(1), SpinUp::from(FanSpinUpConfig::default())).ok();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Locked;
+
+impl sealed::Sealed for Unlocked {
+    const LOCKED: bool = false;
+}
+
+impl sealed::Sealed for Locked {
+    const LOCKED: bool = true;
+}
+impl LockState for Unlocked {}
+impl LockState for Locked {}
@@ -0,0 +1,28 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+/// Output driver of a PWM pin
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// The pin drives both high and low.
+    #[default]
+    PushPull,
+
+    /// The pin only drives low, an external pull-up sets the high level.
+    OpenDrain,
+}
+
+/// Polarity of a PWM output
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Polarity {
+    /// A fan drive setting of 0x00 produces 0% duty cycle.
+    #[default]
+    Normal,
+
+    /// A fan drive setting of 0x00 produces 100% duty cycle.
+    Inverted,
+}
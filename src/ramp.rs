@@ -0,0 +1,109 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    div_round,
+    registers::{MaxStepSize, UpdateTime},
+    Error,
+};
+
+/// Maximum rate at which the drive of a fan changes
+///
+/// The device limits the change of the drive to a number of steps of 1/255 per update period, so
+/// the rate is mapped to the closest combination of step size and update time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RampRate {
+    /// Maximum change of the duty cycle, in percent per second.
+    pub percent_per_second: u16,
+}
+
+impl RampRate {
+    /// Slowest supported rate (percent per second)
+    pub const MIN_PERCENT_PER_SECOND: u16 = 1;
+
+    /// Fastest supported rate (percent per second)
+    pub const MAX_PERCENT_PER_SECOND: u16 = 247;
+
+    /// Largest step the drive can change by in one update period
+    const MAX_STEP: u8 = 0x3F;
+
+    /// Encode the rate into the step size and update time closest to it
+    ///
+    /// When several combinations are equally close, the shortest update time is used.
+    pub(crate) fn to_registers(self) -> Result<(MaxStepSize, UpdateTime), Error> {
+        if !(Self::MIN_PERCENT_PER_SECOND..=Self::MAX_PERCENT_PER_SECOND)
+            .contains(&self.percent_per_second)
+        {
+            return Err(Error::InvalidRampRate);
+        }
+
+        // A step of `step` per `ms` is a rate of step * 100_000 / (255 * ms) percent per second,
+        // so each candidate is compared by its error scaled by 255 * ms.
+        let target = u32::from(self.percent_per_second);
+        let candidate = |update: UpdateTime| {
+            let period = 255 * u32::from(update.millis());
+            let step = div_round(target * period, 100_000).clamp(1, Self::MAX_STEP.into());
+            let error = (step * 100_000).abs_diff(target * period);
+            (step as u8, update, u64::from(error), u64::from(period))
+        };
+
+        let (step, update, _, _) = (1..8).map(UpdateTime::from).map(candidate).fold(
+            candidate(UpdateTime::UpdateTime100ms),
+            |best, next| {
+                if next.2 * best.3 < best.2 * next.3 {
+                    next
+                } else {
+                    best
+                }
+            },
+        );
+        Ok((MaxStepSize::from(step), update))
+    }
+
+    /// Decode the rate from the step size and update time
+    pub(crate) fn from_registers(step: MaxStepSize, update: UpdateTime) -> Self {
+        let step = u32::from(step.stpx() & Self::MAX_STEP);
+        let period = 255 * u32::from(update.millis());
+        Self {
+            percent_per_second: div_round(step * 100_000, period) as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RampRate;
+    use crate::{registers::UpdateTime, Error};
+
+    #[test]
+    fn ramp_rate() {
+        let rate = |percent_per_second| RampRate { percent_per_second };
+
+        let (step, update) = rate(10).to_registers().expect("Could not encode ramp rate");
+        assert_eq!(step.stpx(), 41);
+        assert_eq!(update, UpdateTime::UpdateTime1600ms);
+        assert_eq!(RampRate::from_registers(step, update), rate(10));
+
+        let (step, update) = rate(247)
+            .to_registers()
+            .expect("Could not encode ramp rate");
+        assert_eq!(step.stpx(), 63);
+        assert_eq!(update, UpdateTime::UpdateTime100ms);
+
+        // Every supported rate is reported back within 2% per second
+        for percent_per_second in 1..=247 {
+            let (step, update) = rate(percent_per_second)
+                .to_registers()
+                .expect("Could not encode ramp rate");
+            let decoded = RampRate::from_registers(step, update);
+            assert!(decoded.percent_per_second.abs_diff(percent_per_second) <= 2);
+        }
+
+        assert!(matches!(rate(0).to_registers(), Err(Error::InvalidRampRate)));
+        assert!(matches!(rate(248).to_registers(), Err(Error::InvalidRampRate)));
+    }
+}
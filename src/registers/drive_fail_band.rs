@@ -28,8 +28,11 @@ bitfield::bitfield! {
     pub fxdf, set_fxdf: 7, 0;
 }
 
+/// Drive Fail Band of a fan, combining its low and high byte registers
+///
+/// Converts to and from the 13-bit count held by the registers.
 #[derive(Clone, Copy, Debug)]
-struct DriveFailBand {
+pub struct DriveFailBand {
     low: DriveFailBandLow,
     high: DriveFailBandHigh,
 }
@@ -41,7 +41,8 @@ bitfield::bitfield! {
     pub u8, from into UpdateTime, udtx, set_udtx: 2, 0;
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Minimum RPM range, which sets the TACH count multiplier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum Range {
     Rpm500 = 0b00,
@@ -71,7 +72,8 @@ impl Range {
     }
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Number of TACH edges sampled per RPM measurement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum Edges {
     Sample3 = 0b00,
@@ -110,7 +112,8 @@ impl Edges {
     }
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Update period of the closed loop RPM algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum UpdateTime {
     UpdateTime100ms = 0b000,
@@ -45,9 +45,10 @@ bitfield::bitfield! {
     pub u8, from into ErrorWindow, ergx, set_ergx: 2, 1;
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Derivative term used by the closed loop RPM algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
-pub(crate) enum DerivativeOptions {
+pub enum DerivativeOptions {
     #[default]
     None = 0b00,
     Basic = 0b01,
@@ -55,12 +56,38 @@ pub(crate) enum DerivativeOptions {
     Both = 0b11,
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Error window, in RPM, within which the closed loop RPM algorithm holds its setting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
-pub(crate) enum ErrorWindow {
+pub enum ErrorWindow {
     #[default]
     Rpm0 = 0b00,
     Rpm50 = 0b01,
     Rpm100 = 0b10,
     Rpm200 = 0b11,
 }
+
+impl ErrorWindow {
+    /// Error window which covers exactly `rpm`
+    ///
+    /// Returns `None` if the device does not support the error window.
+    pub fn from_rpm(rpm: u16) -> Option<Self> {
+        match rpm {
+            0 => Some(ErrorWindow::Rpm0),
+            50 => Some(ErrorWindow::Rpm50),
+            100 => Some(ErrorWindow::Rpm100),
+            200 => Some(ErrorWindow::Rpm200),
+            _ => None,
+        }
+    }
+
+    /// Range of the error window in RPM
+    pub fn rpm(&self) -> u16 {
+        match self {
+            ErrorWindow::Rpm0 => 0,
+            ErrorWindow::Rpm50 => 50,
+            ErrorWindow::Rpm100 => 100,
+            ErrorWindow::Rpm200 => 200,
+        }
+    }
+}
@@ -6,7 +6,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use super::RegisterOffset;
-use crate::hacky_round;
+use crate::div_round;
 use emc230x_macros::RegisterOffset;
 
 #[derive(Copy, Clone, Debug, RegisterOffset)]
@@ -15,14 +15,17 @@ pub struct FanDriveSetting(u8);
 
 impl FanDriveSetting {
     pub fn duty_cycle(&self) -> u8 {
-        let duty = (self.0 as f64 / 255.0) * 100.0;
-        hacky_round(duty)
+        div_round(u32::from(self.0) * 100, 255) as u8
     }
 
     pub fn from_duty_cycle(duty: u8) -> Self {
-        let raw = (duty as f64 / 100.0) * 255.0;
-        let raw = hacky_round(raw);
-        FanDriveSetting(raw)
+        let raw = div_round(u32::from(duty) * 255, 100);
+        FanDriveSetting(raw.min(u8::MAX.into()) as u8)
+    }
+
+    /// Setting which produces the same duty cycle on an output with the opposite polarity
+    pub fn inverted(&self) -> Self {
+        FanDriveSetting(!self.0)
     }
 }
 
@@ -38,4 +41,23 @@ mod tests {
         assert_eq!(expected_duty, test_value.duty_cycle());
         assert_eq!(expected_raw, test_value.0);
     }
+
+    #[test]
+    fn duty_cycle_round_trip() {
+        for duty in 0..=100_u8 {
+            let value = FanDriveSetting::from_duty_cycle(duty);
+            assert_eq!(duty, value.duty_cycle());
+
+            // The raw value is the nearest to duty * 2.55, with halves rounded up
+            let error = i32::from(duty) * 510 - i32::from(value.0) * 200;
+            assert!((-100..100).contains(&error), "duty: {}", duty);
+        }
+
+        for raw in 0..=u8::MAX {
+            // The duty cycle is the nearest to raw / 2.55
+            let duty = FanDriveSetting(raw).duty_cycle();
+            let error = i32::from(raw) * 200 - i32::from(duty) * 510;
+            assert!((-255..255).contains(&error), "raw: {}", raw);
+        }
+    }
 }
@@ -18,44 +18,65 @@ bitfield::bitfield! {
     ///
     /// Allows the fan to assert the !ALERT pin if an error condition is detected.
     ///
-    /// 0: Fan is operating within limits.
+    /// 0: Fan errors do not assert the !ALERT pin.
     ///
-    /// 1: Fan has an error condition.
-    pub f5iten, _: 4;
+    /// 1: Fan errors assert the !ALERT pin.
+    pub f5iten, set_f5iten: 4;
 
     /// Fan 4 Interrupt Enable
     ///
     /// Allows the fan to assert the !ALERT pin if an error condition is detected.
     ///
-    /// 0: Fan is operating within limits.
+    /// 0: Fan errors do not assert the !ALERT pin.
     ///
-    /// 1: Fan has an error condition.
-    pub f4iten, _: 3;
+    /// 1: Fan errors assert the !ALERT pin.
+    pub f4iten, set_f4iten: 3;
 
     /// Fan 3 Interrupt Enable
     ///
     /// Allows the fan to assert the !ALERT pin if an error condition is detected.
     ///
-    /// 0: Fan is operating within limits.
+    /// 0: Fan errors do not assert the !ALERT pin.
     ///
-    /// 1: Fan has an error condition.
-    pub f3iten, _: 2;
+    /// 1: Fan errors assert the !ALERT pin.
+    pub f3iten, set_f3iten: 2;
 
     /// Fan 2 Interrupt Enable
     ///
     /// Allows the fan to assert the !ALERT pin if an error condition is detected.
     ///
-    /// 0: Fan is operating within limits.
+    /// 0: Fan errors do not assert the !ALERT pin.
     ///
-    /// 1: Fan has an error condition.
-    pub f2iten, _: 1;
+    /// 1: Fan errors assert the !ALERT pin.
+    pub f2iten, set_f2iten: 1;
 
     /// Fan 1 Interrupt Enable
     ///
     /// Allows the fan to assert the !ALERT pin if an error condition is detected.
     ///
-    /// 0: Fan is operating within limits.
+    /// 0: Fan errors do not assert the !ALERT pin.
     ///
-    /// 1: Fan has an error condition.
-    pub f1iten, _: 0;
+    /// 1: Fan errors assert the !ALERT pin.
+    pub f1iten, set_f1iten: 0;
+}
+
+impl FanInterruptEnable {
+    pub fn enable(&mut self, sel: u8) {
+        self.set_enabled(sel, true);
+    }
+
+    pub fn disable(&mut self, sel: u8) {
+        self.set_enabled(sel, false);
+    }
+
+    fn set_enabled(&mut self, sel: u8, value: bool) {
+        match sel {
+            1 => self.set_f1iten(value),
+            2 => self.set_f2iten(value),
+            3 => self.set_f3iten(value),
+            4 => self.set_f4iten(value),
+            5 => self.set_f5iten(value),
+            _ => {}
+        }
+    }
 }
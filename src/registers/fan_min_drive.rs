@@ -8,7 +8,7 @@
 use bitfield::bitfield;
 
 use super::RegisterOffset;
-use crate::hacky_round;
+use crate::div_round;
 use emc230x_macros::RegisterOffset;
 
 bitfield! {
@@ -25,13 +25,28 @@ bitfield! {
 
 impl FanMinimumDrive {
     pub fn duty_cycle(&self) -> u8 {
-        let duty = (self.0 as f64 / 255.0) * 100.0;
-        hacky_round(duty)
+        div_round(u32::from(self.0) * 100, 255) as u8
     }
 
     pub fn from_duty_cycle(duty: u8) -> Self {
-        let raw = (duty as f64 / 100.0) * 255.0;
-        let raw = hacky_round(raw);
-        FanMinimumDrive(raw)
+        let raw = div_round(u32::from(duty) * 255, 100);
+        FanMinimumDrive(raw.min(u8::MAX.into()) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FanMinimumDrive;
+
+    #[test]
+    fn duty_cycle_round_trip() {
+        for duty in 0..=100_u8 {
+            let value = FanMinimumDrive::from_duty_cycle(duty);
+            assert_eq!(duty, value.duty_cycle());
+
+            // The raw value is the nearest to duty * 2.55, with halves rounded up
+            let error = i32::from(duty) * 510 - i32::from(value.0) * 200;
+            assert!((-100..100).contains(&error), "duty: {}", duty);
+        }
     }
 }
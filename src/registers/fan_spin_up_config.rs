@@ -5,6 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use core::time::Duration;
 use num_enum::{FromPrimitive, IntoPrimitive};
 
 use super::RegisterOffset;
@@ -39,7 +40,8 @@ bitfield::bitfield! {
     pub u8, from into SpinUpTimeMs, spltx, set_spltx: 1, 0;
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Number of update periods used to detect an aging fan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum DriveFailCount {
     #[default]
@@ -49,7 +51,8 @@ pub enum DriveFailCount {
     UpdatePeriod64Ms = 0b11,
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Drive level (in percent) applied during the spin-up routine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum SpinUpLevel {
     Level30 = 0b000,
@@ -63,7 +66,36 @@ pub enum SpinUpLevel {
     Level65 = 0b111,
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+impl SpinUpLevel {
+    /// Lowest supported spin-up level (percent)
+    pub const MIN_PERCENT: u8 = 30;
+
+    /// Highest supported spin-up level (percent)
+    pub const MAX_PERCENT: u8 = 65;
+
+    /// Step between the supported spin-up levels (percent)
+    const STEP_PERCENT: u8 = 5;
+
+    /// Nearest spin-up level to a drive level in percent
+    ///
+    /// Returns `None` if the level is outside of the supported range.
+    pub fn from_percent(percent: u8) -> Option<Self> {
+        if !(Self::MIN_PERCENT..=Self::MAX_PERCENT).contains(&percent) {
+            return None;
+        }
+
+        let step = (percent - Self::MIN_PERCENT + Self::STEP_PERCENT / 2) / Self::STEP_PERCENT;
+        Some(Self::from(step))
+    }
+
+    /// Drive level of the spin-up routine in percent
+    pub fn percent(&self) -> u8 {
+        Self::MIN_PERCENT + u8::from(*self) * Self::STEP_PERCENT
+    }
+}
+
+/// Duration (in milliseconds) of the spin-up routine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum SpinUpTimeMs {
     Time250 = 0b00,
@@ -72,3 +104,66 @@ pub enum SpinUpTimeMs {
     Time1000 = 0b10,
     Time2000 = 0b11,
 }
+
+impl SpinUpTimeMs {
+    /// Shortest supported spin-up time
+    pub const MIN: Duration = Duration::from_millis(250);
+
+    /// Longest supported spin-up time
+    pub const MAX: Duration = Duration::from_millis(2000);
+
+    /// Nearest spin-up time to a duration
+    ///
+    /// Returns `None` if the duration is outside of the supported range.
+    pub fn from_duration(duration: Duration) -> Option<Self> {
+        if !(Self::MIN..=Self::MAX).contains(&duration) {
+            return None;
+        }
+
+        // Each supported time is double the previous one, so split at the midpoints
+        let time = if duration < Duration::from_millis(375) {
+            SpinUpTimeMs::Time250
+        } else if duration < Duration::from_millis(750) {
+            SpinUpTimeMs::Time500
+        } else if duration < Duration::from_millis(1500) {
+            SpinUpTimeMs::Time1000
+        } else {
+            SpinUpTimeMs::Time2000
+        };
+        Some(time)
+    }
+
+    /// Duration of the spin-up routine
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(250 << u8::from(*self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpinUpLevel, SpinUpTimeMs};
+    use core::time::Duration;
+
+    #[test]
+    fn spin_up_level() {
+        assert_eq!(SpinUpLevel::from_percent(30), Some(SpinUpLevel::Level30));
+        assert_eq!(SpinUpLevel::from_percent(52), Some(SpinUpLevel::Level50));
+        assert_eq!(SpinUpLevel::from_percent(53), Some(SpinUpLevel::Level55));
+        assert_eq!(SpinUpLevel::from_percent(65), Some(SpinUpLevel::Level65));
+        assert_eq!(SpinUpLevel::from_percent(29), None);
+        assert_eq!(SpinUpLevel::from_percent(66), None);
+        assert_eq!(SpinUpLevel::Level45.percent(), 45);
+    }
+
+    #[test]
+    fn spin_up_time() {
+        let time = |ms| SpinUpTimeMs::from_duration(Duration::from_millis(ms));
+        assert_eq!(time(250), Some(SpinUpTimeMs::Time250));
+        assert_eq!(time(600), Some(SpinUpTimeMs::Time500));
+        assert_eq!(time(800), Some(SpinUpTimeMs::Time1000));
+        assert_eq!(time(2000), Some(SpinUpTimeMs::Time2000));
+        assert_eq!(time(100), None);
+        assert_eq!(time(2001), None);
+        assert_eq!(SpinUpTimeMs::Time1000.duration(), Duration::from_millis(1000));
+    }
+}
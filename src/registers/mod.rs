@@ -5,31 +5,37 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+//! Typed definitions of the EMC230x registers.
+//!
+//! Each register is a thin wrapper around its raw `u8` value. Registers can be created from a raw
+//! value with [`From<u8>`], start from their power-on value with [`Default`], and are modified
+//! through their typed field setters before being handed to the driver.
+
 use crate::{Error, FanSelect};
-pub(crate) use configuration::Configuration;
-pub(crate) use drive_fail_band::{DriveFailBandHigh, DriveFailBandLow};
-pub(crate) use fan_configuration1::FanConfiguration1;
-pub(crate) use fan_configuration2::FanConfiguration2;
-pub(crate) use fan_drive_fail_status::FanDriveFailStatus;
-pub(crate) use fan_drive_setting::FanDriveSetting;
-pub(crate) use fan_interrupt_enable::FanInterruptEnable;
-pub(crate) use fan_min_drive::FanMinimumDrive;
-pub(crate) use fan_spin_status::FanSpinStatus;
-pub(crate) use fan_spin_up_config::FanSpinUpConfig;
-pub(crate) use fan_stall_status::FanStallStatus;
-pub(crate) use fan_status::FanStatus;
-pub(crate) use max_step_size::MaxStepSize;
-pub(crate) use pid_gain::PidGain;
-pub(crate) use product_features::ProductFeatures;
-pub(crate) use product_id::ProductId;
-pub(crate) use pwm_base::{PwmBase123, PwmBase45};
-pub(crate) use pwm_divide::PwmDivide;
-pub(crate) use pwm_output_config::PwmOutputConfig;
-pub(crate) use pwm_polarity_config::PwmPolarityConfig;
-pub(crate) use software_lock::SoftwareLock;
-pub(crate) use tach_reading::{TachReadingHigh, TachReadingLow};
-pub(crate) use tach_target::{TachTargetHigh, TachTargetLow};
-pub(crate) use valid_tach_count::ValidTachCount;
+pub use configuration::Configuration;
+pub use drive_fail_band::{DriveFailBand, DriveFailBandHigh, DriveFailBandLow};
+pub use fan_configuration1::{Edges, FanConfiguration1, Range, UpdateTime};
+pub use fan_configuration2::{DerivativeOptions, ErrorWindow, FanConfiguration2};
+pub use fan_drive_fail_status::FanDriveFailStatus;
+pub use fan_drive_setting::FanDriveSetting;
+pub use fan_interrupt_enable::FanInterruptEnable;
+pub use fan_min_drive::FanMinimumDrive;
+pub use fan_spin_status::FanSpinStatus;
+pub use fan_spin_up_config::{DriveFailCount, FanSpinUpConfig, SpinUpLevel, SpinUpTimeMs};
+pub use fan_stall_status::FanStallStatus;
+pub use fan_status::FanStatus;
+pub use max_step_size::MaxStepSize;
+pub use pid_gain::{PidGain, PidGainMultiplier};
+pub use product_features::ProductFeatures;
+pub use product_id::ProductId;
+pub use pwm_base::{PwmBase123, PwmBase45, PwmBaseFrequencyKhz};
+pub use pwm_divide::PwmDivide;
+pub use pwm_output_config::PwmOutputConfig;
+pub use pwm_polarity_config::PwmPolarityConfig;
+pub use software_lock::SoftwareLock;
+pub use tach_reading::{TachReading, TachReadingHigh, TachReadingLow};
+pub use tach_target::{TachTarget, TachTargetHigh, TachTargetLow};
+pub use valid_tach_count::ValidTachCount;
 
 pub(crate) mod configuration;
 pub(crate) mod drive_fail_band;
@@ -62,29 +68,19 @@ pub(crate) const FAN3_BASE: u8 = 0x50;
 pub(crate) const FAN4_BASE: u8 = 0x60;
 pub(crate) const FAN5_BASE: u8 = 0x70;
 
-pub(crate) fn fan_register_address(sel: FanSelect, offset: u8) -> Result<u8, Error> {
-    let base = match sel.0 {
-        1 => FAN1_BASE,
-        2 => FAN2_BASE,
-        3 => FAN3_BASE,
-        4 => FAN4_BASE,
-        5 => FAN5_BASE,
-        _ => return Err(Error::InvalidFan),
-    };
-
-    Ok(base + offset)
-}
-
+/// Manufacturer identifier. Always `0x5D` for Microchip/SMSC parts.
 #[derive(Clone, Copy, Debug, emc230x_macros::RegisterAddress)]
 #[register(address = 0xFE, default = 0x5D)]
 pub struct ManufacturerId(u8);
 
 impl ManufacturerId {
+    /// Manufacturer identifier value
     pub fn mfg_id(&self) -> u8 {
         self.0
     }
 }
 
+/// Silicon revision of the device.
 #[derive(Clone, Copy, Debug, emc230x_macros::RegisterAddress)]
 #[register(address = 0xFF, default = 0x80)]
 pub struct SiliconRevision(u8);
@@ -102,7 +98,7 @@ pub(crate) trait RegisterOffset {
     const FAN4_ADDRESS: u8 = FAN4_BASE + Self::OFFSET;
     const FAN5_ADDRESS: u8 = FAN5_BASE + Self::OFFSET;
 
-    #[cfg(test)]
+    /// Address of the register for the selected fan
     fn fan_address(select: FanSelect) -> Result<u8, Error> {
         match select.0 {
             1 => Ok(Self::FAN1_ADDRESS),
@@ -114,3 +110,16 @@ pub(crate) trait RegisterOffset {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Configuration, FanConfiguration1, ProductId};
+    use std::format;
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Configuration::default()), "Configuration(0x40)");
+        assert_eq!(format!("{}", FanConfiguration1::from(0x0B)), "FanConfiguration1(0x0b)");
+        assert_eq!(format!("{}", ProductId::Emc2305), "EMC2305");
+    }
+}
@@ -19,25 +19,42 @@ bitfield::bitfield! {
     /// Derivative Gain
     ///
     /// The effective gain applied to K_D
-    pub gdex, set_gdex: 5, 4;
+    pub u8, from into PidGainMultiplier, gdex, set_gdex: 5, 4;
 
     /// Integral Gain
     ///
     /// The effective gain applied to K_I
-    pub ginx, set_ginx: 3, 2;
+    pub u8, from into PidGainMultiplier, ginx, set_ginx: 3, 2;
 
     /// Proportional Gain
     ///
     /// The effective gain applied to K_P
-    pub gprx, set_gprx: 1, 0;
+    pub u8, from into PidGainMultiplier, gprx, set_gprx: 1, 0;
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Gain multiplier applied to a PID term.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
-pub(crate) enum PidGainMultiplier {
+pub enum PidGainMultiplier {
     X1 = 0b00,
     X2 = 0b01,
     #[default]
     X4 = 0b10,
     X8 = 0b11,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{PidGain, PidGainMultiplier};
+
+    #[test]
+    fn typed_gain_fields() {
+        let mut gain = PidGain::default();
+        assert_eq!(gain.gprx(), PidGainMultiplier::X4);
+
+        gain.set_gprx(PidGainMultiplier::X1);
+        gain.set_ginx(PidGainMultiplier::X2);
+        gain.set_gdex(PidGainMultiplier::X8);
+        assert_eq!(u8::from(gain), 0b0011_0100);
+    }
+}
@@ -5,9 +5,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use core::fmt::{self, Display, Formatter};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-#[derive(Copy, Clone, Debug, IntoPrimitive, TryFromPrimitive)]
+/// Product identifier of the EMC230x family member.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
 pub enum ProductId {
     Emc2305 = 0x34,
@@ -28,15 +30,27 @@ impl ProductId {
             ProductId::Emc2305 => 5,
         }
     }
+
+    /// Part number of the device
+    fn name(&self) -> &'static str {
+        match self {
+            ProductId::Emc2305 => "EMC2305",
+            ProductId::Emc2303 => "EMC2303",
+            ProductId::Emc2302 => "EMC2302",
+            ProductId::Emc2301 => "EMC2301",
+        }
+    }
 }
 
+impl Display for ProductId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(feature = "defmt")]
 impl defmt::Format for ProductId {
     fn format(&self, f: defmt::Formatter) {
-        match self {
-            ProductId::Emc2305 => defmt::write!(f, "EMC2305"),
-            ProductId::Emc2303 => defmt::write!(f, "EMC2303"),
-            ProductId::Emc2302 => defmt::write!(f, "EMC2302"),
-            ProductId::Emc2301 => defmt::write!(f, "EMC2301"),
-        }
+        defmt::write!(f, "{=str}", self.name())
     }
 }
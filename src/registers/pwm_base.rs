@@ -10,9 +10,10 @@ use num_enum::{FromPrimitive, IntoPrimitive};
 use super::RegisterAddress;
 use emc230x_macros::RegisterAddress;
 
-#[derive(Clone, Copy, Debug, FromPrimitive, IntoPrimitive)]
+/// Base frequency of the PWM output before the PWM divider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, IntoPrimitive)]
 #[repr(u8)]
-enum PwmBaseFrequencyKhz {
+pub enum PwmBaseFrequencyKhz {
     Pwm2_441 = 0b11,
     Pwm4_882 = 0b10,
     Pwm19_53 = 0b01,
@@ -20,6 +21,58 @@ enum PwmBaseFrequencyKhz {
     Pwm26_00 = 0b00,
 }
 
+impl PwmBaseFrequencyKhz {
+    /// Lowest PWM output frequency (Hz) the device can produce
+    pub const MIN_OUTPUT_HZ: u32 = 10;
+
+    /// Highest PWM output frequency (Hz) the device can produce
+    pub const MAX_OUTPUT_HZ: u32 = 26_000;
+
+    /// Base frequencies from highest to lowest
+    const ALL: [PwmBaseFrequencyKhz; 4] = [
+        PwmBaseFrequencyKhz::Pwm26_00,
+        PwmBaseFrequencyKhz::Pwm19_53,
+        PwmBaseFrequencyKhz::Pwm4_882,
+        PwmBaseFrequencyKhz::Pwm2_441,
+    ];
+
+    /// Base frequency in Hz
+    pub fn hz(&self) -> u32 {
+        match self {
+            PwmBaseFrequencyKhz::Pwm2_441 => 2_441,
+            PwmBaseFrequencyKhz::Pwm4_882 => 4_882,
+            PwmBaseFrequencyKhz::Pwm19_53 => 19_531,
+            PwmBaseFrequencyKhz::Pwm26_00 => 26_000,
+        }
+    }
+
+    /// PWM output frequency (Hz) produced with the specified PWM Divide value
+    ///
+    /// A divide value of 0 is interpreted as 1 by the device.
+    pub fn output_hz(&self, divide: u8) -> u32 {
+        let divide = u32::from(divide.max(1));
+        (self.hz() + divide / 2) / divide
+    }
+
+    /// Base frequency and PWM Divide value which produce the output frequency closest to `hz`
+    ///
+    /// Returns `None` if the frequency is outside of the range the device can produce. When
+    /// several pairs are equally close, the highest base frequency is used.
+    pub fn closest(hz: u32) -> Option<(Self, u8)> {
+        if !(Self::MIN_OUTPUT_HZ..=Self::MAX_OUTPUT_HZ).contains(&hz) {
+            return None;
+        }
+
+        Self::ALL
+            .iter()
+            .map(|base| {
+                let divide = ((base.hz() + hz / 2) / hz).clamp(1, u8::MAX as u32) as u8;
+                (*base, divide)
+            })
+            .min_by_key(|(base, divide)| base.output_hz(*divide).abs_diff(hz))
+    }
+}
+
 bitfield::bitfield! {
     #[derive(Clone, Copy, RegisterAddress)]
     #[register(address = 0x2C, default = 0x00)]
@@ -48,3 +101,71 @@ bitfield::bitfield! {
     /// PWM1 Base Frequency
     pub u8, from into PwmBaseFrequencyKhz, pmb1, set_pmb1: 1, 0;
 }
+
+impl PwmBase45 {
+    /// Base frequency of the selected fan, if the fan is part of this register
+    pub fn base(&self, sel: u8) -> Option<PwmBaseFrequencyKhz> {
+        match sel {
+            4 => Some(self.pmb4()),
+            5 => Some(self.pmb5()),
+            _ => None,
+        }
+    }
+
+    /// Set the base frequency of the selected fan, leaving the other fan unchanged
+    pub fn set_base(&mut self, sel: u8, base: PwmBaseFrequencyKhz) {
+        match sel {
+            4 => self.set_pmb4(base),
+            5 => self.set_pmb5(base),
+            _ => {}
+        }
+    }
+}
+
+impl PwmBase123 {
+    /// Base frequency of the selected fan, if the fan is part of this register
+    pub fn base(&self, sel: u8) -> Option<PwmBaseFrequencyKhz> {
+        match sel {
+            1 => Some(self.pmb1()),
+            2 => Some(self.pmb2()),
+            3 => Some(self.pmb3()),
+            _ => None,
+        }
+    }
+
+    /// Set the base frequency of the selected fan, leaving the other fans unchanged
+    pub fn set_base(&mut self, sel: u8, base: PwmBaseFrequencyKhz) {
+        match sel {
+            1 => self.set_pmb1(base),
+            2 => self.set_pmb2(base),
+            3 => self.set_pmb3(base),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PwmBase123, PwmBaseFrequencyKhz};
+
+    #[test]
+    fn closest_frequency() {
+        assert_eq!(PwmBaseFrequencyKhz::closest(25_000), Some((PwmBaseFrequencyKhz::Pwm26_00, 1)));
+        assert_eq!(PwmBaseFrequencyKhz::closest(1_000), Some((PwmBaseFrequencyKhz::Pwm26_00, 26)));
+        assert_eq!(PwmBaseFrequencyKhz::closest(10), Some((PwmBaseFrequencyKhz::Pwm2_441, 244)));
+        assert_eq!(PwmBaseFrequencyKhz::closest(50), Some((PwmBaseFrequencyKhz::Pwm4_882, 98)));
+        assert_eq!(PwmBaseFrequencyKhz::closest(9), None);
+        assert_eq!(PwmBaseFrequencyKhz::closest(30_000), None);
+        assert_eq!(PwmBaseFrequencyKhz::Pwm4_882.output_hz(0), 4_882);
+    }
+
+    #[test]
+    fn per_fan_base() {
+        let mut base = PwmBase123::from(0x00);
+        base.set_base(2, PwmBaseFrequencyKhz::Pwm2_441);
+        assert_eq!(u8::from(base), 0b0000_1100);
+        assert_eq!(base.base(2), Some(PwmBaseFrequencyKhz::Pwm2_441));
+        assert_eq!(base.base(1), Some(PwmBaseFrequencyKhz::Pwm26_00));
+        assert_eq!(base.base(4), None);
+    }
+}
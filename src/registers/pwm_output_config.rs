@@ -72,4 +72,16 @@ impl PwmOutputConfig {
             _ => {}
         }
     }
+
+    /// Determine if the output of the selected fan is push-pull
+    pub fn is_push_pull(&self, sel: u8) -> bool {
+        match sel {
+            1 => self.pmot1(),
+            2 => self.pmot2(),
+            3 => self.pmot3(),
+            4 => self.pmot4(),
+            5 => self.pmot5(),
+            _ => false,
+        }
+    }
 }
@@ -59,3 +59,29 @@ bitfield::bitfield! {
     ///    0% duty cycle.
     pub plrity1, set_plrity1: 0;
 }
+
+impl PwmPolarityConfig {
+    /// Determine if the output of the selected fan is inverted
+    pub fn inverted(&self, sel: u8) -> bool {
+        match sel {
+            1 => self.plrity1(),
+            2 => self.plrity2(),
+            3 => self.plrity3(),
+            4 => self.plrity4(),
+            5 => self.plrity5(),
+            _ => false,
+        }
+    }
+
+    /// Set whether the output of the selected fan is inverted
+    pub fn set_inverted(&mut self, sel: u8, inverted: bool) {
+        match sel {
+            1 => self.set_plrity1(inverted),
+            2 => self.set_plrity2(inverted),
+            3 => self.set_plrity3(inverted),
+            4 => self.set_plrity4(inverted),
+            5 => self.set_plrity5(inverted),
+            _ => {}
+        }
+    }
+}
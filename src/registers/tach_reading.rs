@@ -28,6 +28,9 @@ bitfield::bitfield! {
     pub fxtr, set_fxtr: 7, 0;
 }
 
+/// TACH Reading of a fan, combining its low and high byte registers
+///
+/// Converts to and from the 13-bit count held by the registers.
 #[derive(Clone, Copy, Debug)]
 pub struct TachReading {
     low: TachReadingLow,
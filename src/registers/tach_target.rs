@@ -28,8 +28,11 @@ bitfield::bitfield! {
     pub fxtt, set_fxtt: 7, 0;
 }
 
+/// TACH Target of a fan, combining its low and high byte registers
+///
+/// Converts to and from the 13-bit count held by the registers.
 #[derive(Clone, Copy, Debug)]
-struct TachTarget {
+pub struct TachTarget {
     low: TachTargetLow,
     high: TachTargetHigh,
 }
@@ -6,6 +6,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use super::RegisterOffset;
+use crate::div_round;
 use emc230x_macros::RegisterOffset;
 
 bitfield::bitfield! {
@@ -22,4 +23,25 @@ impl ValidTachCount {
     pub fn max_tach_count(&self) -> u16 {
         (self.0 as u16) << 5_u16
     }
+
+    /// Valid TACH Count closest to a maximum TACH count
+    ///
+    /// Counts above the largest representable count are limited to it.
+    pub fn from_max_tach_count(count: u16) -> Self {
+        let value = div_round(u32::from(count), 1 << 5).min(u32::from(u8::MAX));
+        Self(value as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidTachCount;
+
+    #[test]
+    fn max_tach_count() {
+        assert_eq!(ValidTachCount::from_max_tach_count(7840).0, 0xF5);
+        assert_eq!(ValidTachCount::from_max_tach_count(7864).0, 0xF6);
+        assert_eq!(ValidTachCount::from_max_tach_count(0x1FFF).0, 0xFF);
+        assert_eq!(ValidTachCount::from(0xF5).max_tach_count(), 7840);
+    }
 }
@@ -0,0 +1,31 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::FanRpm;
+
+/// Speed of a fan derived from its TACH reading
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FanSpeed {
+    /// The fan is running at the contained RPM.
+    Running(FanRpm),
+
+    /// The fan is stopped or not connected, no TACH pulses are measured.
+    Stopped,
+
+    /// The fan is turning slower than the Valid TACH Count allows, so its RPM is not reliable.
+    BelowMeasurableRange,
+}
+
+impl FanSpeed {
+    /// RPM of the fan, if it is running
+    pub fn rpm(&self) -> Option<FanRpm> {
+        match self {
+            FanSpeed::Running(rpm) => Some(*rpm),
+            FanSpeed::Stopped | FanSpeed::BelowMeasurableRange => None,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use core::time::Duration;
+
+use crate::{
+    registers::{DriveFailCount, FanSpinUpConfig, SpinUpLevel, SpinUpTimeMs},
+    Error,
+};
+
+/// Spin-up routine used to start a fan from a stop
+///
+/// The device supports levels of 30% to 65% in steps of 5%, and times of 250 ms, 500 ms, 1 s and
+/// 2 s. Values in between are mapped to the nearest supported setting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpinUp {
+    /// Drive level (in percent) applied during the spin-up routine.
+    pub level_percent: u8,
+
+    /// How long the spin-up routine runs before releasing the drive.
+    pub duration: Duration,
+
+    /// Drive the fan at 100% duty cycle for the first 1/4 of the spin-up time.
+    pub kick: bool,
+
+    /// Number of update periods used to detect an aging fan.
+    pub drive_fail_count: DriveFailCount,
+}
+
+impl SpinUp {
+    /// Encode the spin-up routine into the register value
+    pub(crate) fn to_register(self) -> Result<FanSpinUpConfig, Error> {
+        let level =
+            SpinUpLevel::from_percent(self.level_percent).ok_or(Error::InvalidSpinUpLevel)?;
+        let time = SpinUpTimeMs::from_duration(self.duration).ok_or(Error::InvalidSpinUpTime)?;
+
+        let mut value = FanSpinUpConfig::from(0x00);
+        value.set_dfcx(self.drive_fail_count);
+        value.set_nkckx(!self.kick);
+        value.set_splvx(level);
+        value.set_spltx(time);
+        Ok(value)
+    }
+}
+
+impl From<FanSpinUpConfig> for SpinUp {
+    fn from(value: FanSpinUpConfig) -> Self {
+        Self {
+            level_percent: value.splvx().percent(),
+            duration: value.spltx().duration(),
+            kick: !value.nkckx(),
+            drive_fail_count: value.dfcx(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpinUp;
+    use crate::{registers::DriveFailCount, Error};
+    use core::time::Duration;
+
+    #[test]
+    fn spin_up() {
+        let spin_up = SpinUp {
+            level_percent: 47,
+            duration: Duration::from_millis(900),
+            kick: false,
+            drive_fail_count: DriveFailCount::UpdatePeriod32Ms,
+        };
+        let value = spin_up.to_register().expect("Could not encode spin-up");
+        assert_eq!(u8::from(value), 0b1010_1110);
+
+        // Decoding reports the setting the device uses
+        let decoded = SpinUp::from(value);
+        assert_eq!(decoded.level_percent, 45);
+        assert_eq!(decoded.duration, Duration::from_secs(1));
+        assert_eq!(decoded.drive_fail_count, DriveFailCount::UpdatePeriod32Ms);
+        assert!(!decoded.kick);
+
+        let invalid = SpinUp {
+            level_percent: 70,
+            ..spin_up
+        };
+        assert!(matches!(invalid.to_register(), Err(Error::InvalidSpinUpLevel)));
+        let invalid = SpinUp {
+            duration: Duration::from_secs(3),
+            ..spin_up
+        };
+        assert!(matches!(invalid.to_register(), Err(Error::InvalidSpinUpTime)));
+    }
+}
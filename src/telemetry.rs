@@ -0,0 +1,65 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{FanDutyCycle, FanFaults, FanRpm, FanSelect, FanSpeed};
+
+/// State of a single fan, as read by a snapshot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FanTelemetry {
+    /// Duty cycle the fan is driven at.
+    pub duty_cycle: FanDutyCycle,
+
+    /// Speed of the fan derived from its TACH reading.
+    pub speed: FanSpeed,
+
+    /// Target RPM of the fan, used while it is in RPM mode.
+    pub target_rpm: FanRpm,
+
+    /// The fan is driven by the RPM control algorithm instead of a fixed duty cycle.
+    pub rpm_mode: bool,
+
+    /// Faults reported for the fan which have not been acknowledged yet.
+    pub faults: FanFaults,
+}
+
+impl Default for FanTelemetry {
+    fn default() -> Self {
+        Self {
+            duty_cycle: 0,
+            speed: FanSpeed::Stopped,
+            target_rpm: 0,
+            rpm_mode: false,
+            faults: FanFaults::default(),
+        }
+    }
+}
+
+/// State of every fan on the device, read with a few bus transactions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    fans: [FanTelemetry; 5],
+    count: u8,
+}
+
+impl Snapshot {
+    pub(crate) fn new(fans: [FanTelemetry; 5], count: u8) -> Self {
+        Self {
+            fans,
+            count: count.min(5),
+        }
+    }
+
+    /// State of every fan the device supports, starting with fan 1
+    pub fn fans(&self) -> &[FanTelemetry] {
+        &self.fans[..usize::from(self.count)]
+    }
+
+    /// State of the selected fan, if the device supports it
+    pub fn fan(&self, sel: FanSelect) -> Option<&FanTelemetry> {
+        self.fans().get(usize::from(sel.0).checked_sub(1)?)
+    }
+}
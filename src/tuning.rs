@@ -0,0 +1,76 @@
+// Copyright (c) 2024 Jake Swensen
+// SPDX-License-Identifier: MPL-2.0
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    registers::{
+        DerivativeOptions, ErrorWindow, FanConfiguration1, FanConfiguration2, PidGain,
+        PidGainMultiplier, UpdateTime,
+    },
+    Error,
+};
+
+/// Tuning of the closed loop RPM control algorithm of a fan
+///
+/// The settings are spread across the PID Gain, Fan Configuration 2 and Fan Configuration 1
+/// registers of the fan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tuning {
+    /// Gain applied to the proportional term.
+    pub kp: PidGainMultiplier,
+
+    /// Gain applied to the integral term.
+    pub ki: PidGainMultiplier,
+
+    /// Gain applied to the derivative term.
+    pub kd: PidGainMultiplier,
+
+    /// Form of the derivative used in the algorithm.
+    pub derivative: DerivativeOptions,
+
+    /// Error (in RPM) within which the drive is not adjusted. One of 0, 50, 100 or 200.
+    pub error_window_rpm: u16,
+
+    /// Update rate of the algorithm.
+    pub update_time: UpdateTime,
+}
+
+impl Tuning {
+    /// Apply the tuning to the current register values, keeping the unrelated fields
+    pub(crate) fn apply(
+        &self,
+        gain: &mut PidGain,
+        cfg2: &mut FanConfiguration2,
+        cfg1: &mut FanConfiguration1,
+    ) -> Result<(), Error> {
+        let window =
+            ErrorWindow::from_rpm(self.error_window_rpm).ok_or(Error::InvalidErrorWindow)?;
+
+        gain.set_gprx(self.kp);
+        gain.set_ginx(self.ki);
+        gain.set_gdex(self.kd);
+        cfg2.set_dptx(self.derivative);
+        cfg2.set_ergx(window);
+        cfg1.set_udtx(self.update_time);
+        Ok(())
+    }
+
+    /// Decode the tuning from the register values
+    pub(crate) fn from_registers(
+        gain: PidGain,
+        cfg2: FanConfiguration2,
+        cfg1: FanConfiguration1,
+    ) -> Self {
+        Self {
+            kp: gain.gprx(),
+            ki: gain.ginx(),
+            kd: gain.gdex(),
+            derivative: cfg2.dptx(),
+            error_window_rpm: cfg2.ergx().rpm(),
+            update_time: cfg1.udtx(),
+        }
+    }
+}